@@ -1,5 +1,5 @@
+use atlas_rb_tree::{ordered_set::OrderedSet, top_down::TopDownTree, tree::Tree};
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
-use atlas_rb_tree::tree::Tree;
 
 fn insert_benchmark(c: &mut Criterion) {
     c.bench_function("Tree insert", |b| {
@@ -22,5 +22,29 @@ fn delete_benchmark(c: &mut Criterion) {
     });
 }
 
-criterion_group!(benches, insert_benchmark, delete_benchmark);
+/// Inserts and then deletes 1000 keys in a scrambled order.
+fn insert_delete<S: OrderedSet<u32>>(mut set: S) {
+    for key in 0..1000 {
+        set.insert(black_box((key * 7919) % 1000));
+    }
+    for key in 0..1000 {
+        set.delete(black_box(key));
+    }
+}
+
+fn bottom_up_vs_top_down_benchmark(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Bottom-up vs top-down");
+    group.bench_function("Tree", |b| b.iter(|| insert_delete(Tree::new())));
+    group.bench_function("TopDownTree", |b| {
+        b.iter(|| insert_delete(TopDownTree::new()))
+    });
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    insert_benchmark,
+    delete_benchmark,
+    bottom_up_vs_top_down_benchmark
+);
 criterion_main!(benches);
@@ -0,0 +1,651 @@
+//! Maps that keep a summary of the values in every subtree, and can change
+//! all values in a range of keys lazily.
+//!
+//! The summary and the pending change of a node live in its key, an
+//! `Entry`, rather than in fields of the node. `Node<T>` is shared by every
+//! tree in the crate, and giving it a pending tag would add a type parameter
+//! for the change to all of them. The tree passes the change on through the
+//! `push_down` hook of its augmentation instead, at the same points where a
+//! tag on the node would be pushed down: rotations, and the descents to nodes
+//! that are linked or unlinked.
+
+use crate::tree::{compare, Augmentation, Children, Position, Tree};
+use alloc::{rc::Rc, vec::Vec};
+use core::{
+    cell::RefCell,
+    cmp::Ordering,
+    fmt::{self, Debug, Formatter},
+    marker::PhantomData,
+    mem,
+    ops::{Add, Bound, RangeBounds},
+};
+
+#[cfg(test)]
+mod aggregate_tests;
+
+type NodeRef<K, V, A, U> = Rc<RefCell<crate::node::Node<Entry<K, V, A, U>>>>;
+
+/// A node and the update pending in its ancestors.
+type Frame<K, V, A, U> = (NodeRef<K, V, A, U>, Option<U>);
+
+/// A way to summarize runs of values, such that the summary of a run can be
+/// computed from the summaries of any two parts it is split into.
+///
+/// `combine` must be associative and `identity` must be its neutral element,
+/// that is, the summary of no values.
+pub trait Aggregate<V> {
+    type Summary: Clone + Debug;
+
+    fn identity() -> Self::Summary;
+
+    /// Returns the summary of the single value `value`.
+    fn summarize(value: &V) -> Self::Summary;
+
+    /// Returns the summary of the run `left` followed by the run `right`.
+    fn combine(left: &Self::Summary, right: &Self::Summary) -> Self::Summary;
+}
+
+/// A change that [`AggregateMap::update_range`] applies to every value in a
+/// range of keys.
+///
+/// The change is not applied to each value right away. It is applied to the
+/// summaries of the O(log n) subtrees that cover the range, and left pending
+/// in their roots until a later operation needs to reach below them.
+pub trait Update<V, A: Aggregate<V>>: Clone + Debug {
+    /// Applies the change to a single value.
+    fn apply(&self, value: &mut V);
+
+    /// Applies the change to the summary of `len` values, which must give
+    /// the summary of the changed values.
+    fn apply_to_summary(&self, summary: &mut A::Summary, len: usize);
+
+    /// Returns the change that makes `self` followed by `later`.
+    fn then(&self, later: &Self) -> Self;
+}
+
+/// Sums the values, starting from `V::default()`.
+pub struct Sum;
+
+impl<V: Add<Output = V> + Clone + Debug + Default> Aggregate<V> for Sum {
+    type Summary = V;
+
+    fn identity() -> V {
+        V::default()
+    }
+
+    fn summarize(value: &V) -> V {
+        value.clone()
+    }
+
+    fn combine(left: &V, right: &V) -> V {
+        left.clone() + right.clone()
+    }
+}
+
+/// The update type of an [`AggregateMap`] without range updates. It has no
+/// values, so [`AggregateMap::update_range`] cannot be called.
+#[derive(Clone, Debug)]
+pub enum NoUpdate {}
+
+impl<V, A: Aggregate<V>> Update<V, A> for NoUpdate {
+    fn apply(&self, _: &mut V) {
+        match *self {}
+    }
+
+    fn apply_to_summary(&self, _: &mut A::Summary, _: usize) {
+        match *self {}
+    }
+
+    fn then(&self, _: &Self) -> Self {
+        match *self {}
+    }
+}
+
+/// Adds the same offset to every value, keeping their [`Sum`] up to date.
+///
+/// Updating a subtree with more elements than an integer value type can
+/// count panics instead of truncating the count.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Offset<V>(pub V);
+
+/// Implements [`Update`] for [`Offset`] over the given value types, with
+/// `$count` turning a subtree size into the value type.
+macro_rules! impl_offset {
+    ($count:expr; $($t:ty),*) => {
+        $(
+            impl Update<$t, Sum> for Offset<$t> {
+                fn apply(&self, value: &mut $t) {
+                    *value += self.0;
+                }
+
+                fn apply_to_summary(&self, summary: &mut $t, len: usize) {
+                    let count: fn(usize) -> $t = $count;
+                    *summary += self.0 * count(len);
+                }
+
+                fn then(&self, later: &Self) -> Self {
+                    Offset(self.0 + later.0)
+                }
+            }
+        )*
+    };
+}
+
+impl_offset!(
+    |len| <_>::try_from(len).expect("Subtree size does not fit the offset type");
+    i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128
+);
+impl_offset!(|len| len as _; usize, f32, f64);
+
+/// A key and its value, ordered and compared by the key alone, together with
+/// the summary of the values in the subtree of its node and the update still
+/// owed to the nodes below it, which is the pending tag of the node.
+struct Entry<K, V, A: Aggregate<V>, U> {
+    key: K,
+    value: V,
+    summary: A::Summary,
+    /// Already applied to `value` and `summary`, but not yet to the children.
+    pending: Option<U>,
+}
+
+impl<K: Clone, V: Clone, A: Aggregate<V>, U: Clone> Clone for Entry<K, V, A, U> {
+    fn clone(&self) -> Self {
+        Self {
+            key: self.key.clone(),
+            value: self.value.clone(),
+            summary: self.summary.clone(),
+            pending: self.pending.clone(),
+        }
+    }
+}
+
+impl<K: Default, V: Default, A: Aggregate<V>, U> Default for Entry<K, V, A, U> {
+    fn default() -> Self {
+        Self {
+            key: K::default(),
+            value: V::default(),
+            summary: A::identity(),
+            pending: None,
+        }
+    }
+}
+
+impl<K: Debug, V: Debug, A: Aggregate<V>, U: Debug> Debug for Entry<K, V, A, U> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Entry")
+            .field("key", &self.key)
+            .field("value", &self.value)
+            .field("summary", &self.summary)
+            .field("pending", &self.pending)
+            .finish()
+    }
+}
+
+impl<K: PartialEq, V, A: Aggregate<V>, U> PartialEq for Entry<K, V, A, U> {
+    fn eq(&self, other: &Self) -> bool {
+        self.key == other.key
+    }
+}
+
+impl<K: PartialOrd, V, A: Aggregate<V>, U> PartialOrd for Entry<K, V, A, U> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.key.partial_cmp(&other.key)
+    }
+}
+
+/// A map from unique keys to values that also keeps, in every node of its
+/// red-black tree, the [`Aggregate`] of the values in the node's subtree.
+///
+/// This answers [`AggregateMap::fold_range`] by combining O(log n) subtree
+/// summaries instead of visiting every value in the range. With an
+/// [`Update`] type `U`, [`AggregateMap::update_range`] changes every value
+/// in a range in O(log n) as well.
+///
+/// ```
+/// use atlas_rb_tree::aggregate::{AggregateMap, Offset, Sum};
+///
+/// let mut sales: AggregateMap<u32, i64, Sum, Offset<i64>> = AggregateMap::new();
+/// for (day, amount) in [(1, 10), (2, 20), (3, 5), (7, 1)] {
+///     sales.insert(day, amount);
+/// }
+///
+/// assert_eq!(sales.sum_range(2..7), 25);
+/// assert_eq!(sales.count_range(2..=7), 3);
+///
+/// sales.update_range(2..=3, Offset(100));
+/// assert_eq!(sales.sum_range(2..7), 225);
+/// assert_eq!(sales.get(&3), Some(105));
+/// ```
+pub struct AggregateMap<K, V, A: Aggregate<V> = Sum, U = NoUpdate> {
+    tree: Tree<Entry<K, V, A, U>>,
+    aggregate: PhantomData<A>,
+}
+
+/// An iterator over the entries of an [`AggregateMap`] in ascending key
+/// order. Created by [`AggregateMap::iter`].
+///
+/// The values are yielded by value, with the updates still pending above
+/// them applied.
+pub struct Iter<'a, K, V, A: Aggregate<V>, U> {
+    tree: &'a Tree<Entry<K, V, A, U>>,
+    /// The nodes whose left subtree is being visited, each with the updates
+    /// pending in its ancestors.
+    stack: Vec<Frame<K, V, A, U>>,
+}
+
+/// Recomputes the summary of `entry` from its value and the summaries of its
+/// children.
+fn update<K, V, A: Aggregate<V>, U>(
+    entry: &mut Entry<K, V, A, U>,
+    left: Option<&Entry<K, V, A, U>>,
+    right: Option<&Entry<K, V, A, U>>,
+) {
+    let mut summary = A::summarize(&entry.value);
+    if let Some(left) = left {
+        summary = A::combine(&left.summary, &summary);
+    }
+    if let Some(right) = right {
+        summary = A::combine(&summary, &right.summary);
+    }
+    entry.summary = summary;
+}
+
+/// Applies the update pending in `entry` to its children, given with their
+/// subtree sizes.
+fn push_down<K, V, A: Aggregate<V>, U: Update<V, A>>(
+    entry: &mut Entry<K, V, A, U>,
+    children: Children<'_, Entry<K, V, A, U>>,
+) {
+    let Some(update) = entry.pending.take() else {
+        return;
+    };
+    for (child, len) in children.into_iter().flatten() {
+        apply(child, &update, len);
+    }
+}
+
+/// Applies `update` to the node holding `entry` and leaves it pending for
+/// the `len - 1` nodes below.
+fn apply<K, V, A: Aggregate<V>, U: Update<V, A>>(
+    entry: &mut Entry<K, V, A, U>,
+    update: &U,
+    len: usize,
+) {
+    update.apply(&mut entry.value);
+    update.apply_to_summary(&mut entry.summary, len);
+    entry.pending = Some(match &entry.pending {
+        Some(earlier) => earlier.then(update),
+        None => update.clone(),
+    });
+}
+
+/// Returns the update made by `earlier`, then `later`.
+fn then<V, A: Aggregate<V>, U: Update<V, A>>(earlier: Option<&U>, later: Option<&U>) -> Option<U> {
+    match (earlier, later) {
+        (Some(earlier), Some(later)) => Some(earlier.then(later)),
+        (earlier, later) => earlier.or(later).cloned(),
+    }
+}
+
+/// Whether `key` comes before `range`, within it, or after it. The start and
+/// end bound are only checked if `check_start` and `check_end` are set.
+fn locate<K: PartialOrd, R: RangeBounds<K>>(
+    key: &K,
+    range: &R,
+    check_start: bool,
+    check_end: bool,
+) -> Ordering {
+    let before_start = check_start
+        && match range.start_bound() {
+            Bound::Included(start) => key < start,
+            Bound::Excluded(start) => key <= start,
+            Bound::Unbounded => false,
+        };
+    let after_end = check_end
+        && match range.end_bound() {
+            Bound::Included(end) => end < key,
+            Bound::Excluded(end) => end <= key,
+            Bound::Unbounded => false,
+        };
+    if before_start {
+        Ordering::Less
+    } else if after_end {
+        Ordering::Greater
+    } else {
+        Ordering::Equal
+    }
+}
+
+impl<K, V, A, U> AggregateMap<K, V, A, U>
+where
+    K: PartialOrd + Clone + Debug + Default,
+    V: Clone + Debug + Default,
+    A: Aggregate<V>,
+    U: Update<V, A>,
+{
+    pub fn new() -> AggregateMap<K, V, A, U> {
+        Self {
+            tree: Tree::with_augmentation(Augmentation {
+                update: update::<K, V, A, U>,
+                push_down: Some(push_down::<K, V, A, U>),
+            }),
+            aggregate: PhantomData,
+        }
+    }
+
+    /// Inserts `value` under `key` and returns the value it replaces, if any.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        match self.position(&key) {
+            Position::Found(node) => {
+                self.tree.push_path(&node);
+                let old = mem::replace(&mut self.tree.key_mut(&node).value, value);
+                self.tree.update_path(&node);
+                Some(old)
+            }
+            Position::Vacant { parent, as_left } => {
+                let entry = Entry {
+                    key,
+                    value,
+                    ..Entry::default()
+                };
+                self.tree.insert_vacant(parent, as_left, entry);
+                None
+            }
+        }
+    }
+
+    /// Returns the value of `key`. The value is returned by copy since the
+    /// stored one may still lack updates that are pending above it.
+    pub fn get(&self, key: &K) -> Option<V> {
+        let mut node = self.tree.root_node().clone();
+        let mut pending: Option<U> = None;
+        while !node.borrow().is_nil() {
+            let node_tmp = {
+                let node = node.borrow();
+                match compare(key, &node.key.key) {
+                    Ordering::Equal => {
+                        let mut value = node.key.value.clone();
+                        if let Some(update) = &pending {
+                            update.apply(&mut value);
+                        }
+                        return Some(value);
+                    }
+                    Ordering::Less => node.left().clone(),
+                    Ordering::Greater => node.right().clone(),
+                }
+            };
+            pending = then::<V, A, U>(node.borrow().key.pending.as_ref(), pending.as_ref());
+            node = node_tmp;
+        }
+        None
+    }
+
+    /// Calls `f` on the value of `key` and updates the summaries. Returns
+    /// `false` if `key` is absent.
+    pub fn update<F: FnOnce(&mut V)>(&mut self, key: &K, f: F) -> bool {
+        match self.position(key) {
+            Position::Found(node) => {
+                self.tree.push_path(&node);
+                f(&mut self.tree.key_mut(&node).value);
+                self.tree.update_path(&node);
+                true
+            }
+            Position::Vacant { .. } => false,
+        }
+    }
+
+    pub fn contains_key(&self, key: &K) -> bool {
+        matches!(self.position(key), Position::Found(_))
+    }
+
+    /// Removes `key` and returns its value, if it was present.
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        match self.position(key) {
+            Position::Found(node) => Some(self.tree.remove_node(node).value),
+            Position::Vacant { .. } => None,
+        }
+    }
+
+    /// Applies `update` to every value whose key is within `range`, in
+    /// O(log n).
+    pub fn update_range<R: RangeBounds<K>>(&mut self, range: R, update: U) {
+        let root = self.tree.root_node().clone();
+        self.update_within(&root, &range, &update, true, true);
+    }
+
+    /// Returns the number of keys within `range` in O(log n).
+    pub fn count_range<R: RangeBounds<K>>(&self, range: R) -> usize {
+        let entry = |key: &K| Entry {
+            key: key.clone(),
+            ..Entry::default()
+        };
+        self.tree
+            .count_range((range.start_bound().map(entry), range.end_bound().map(entry)))
+    }
+
+    /// Returns the aggregate of the values whose keys are within `range`, in
+    /// O(log n).
+    pub fn fold_range<R: RangeBounds<K>>(&self, range: R) -> A::Summary {
+        fold_within(self.tree.root_node(), &range, None, true, true)
+    }
+
+    /// Returns the aggregate of all values in O(1).
+    pub fn total(&self) -> A::Summary {
+        // The summary of a node already includes its own pending update.
+        let root = self.tree.root_node().borrow();
+        if root.is_nil() {
+            A::identity()
+        } else {
+            root.key.summary.clone()
+        }
+    }
+
+    /// Returns an iterator over the entries in ascending key order.
+    pub fn iter(&self) -> Iter<'_, K, V, A, U> {
+        let mut iter = Iter {
+            tree: &self.tree,
+            stack: Vec::new(),
+        };
+        iter.push_left(self.tree.root_node().clone(), None);
+        iter
+    }
+
+    pub fn len(&self) -> usize {
+        self.tree.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tree.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.tree.clear();
+    }
+
+    fn position(&self, key: &K) -> Position<Entry<K, V, A, U>> {
+        self.tree.position_by(|entry| compare(key, &entry.key))
+    }
+
+    /// Applies `update` to the values below `node` whose keys are within
+    /// `range`, visiting the same nodes as [`AggregateMap::fold_range`].
+    fn update_within<R: RangeBounds<K>>(
+        &mut self,
+        node: &NodeRef<K, V, A, U>,
+        range: &R,
+        update: &U,
+        check_start: bool,
+        check_end: bool,
+    ) {
+        if node.borrow().is_nil() {
+            return;
+        }
+        if !check_start && !check_end {
+            let len = node.borrow().size;
+            apply(&mut node.borrow_mut().key, update, len);
+            return;
+        }
+
+        self.tree.push_down(node);
+        let location = locate(&node.borrow().key.key, range, check_start, check_end);
+        let left = node.borrow().left().clone();
+        let right = node.borrow().right().clone();
+        match location {
+            Ordering::Less => self.update_within(&right, range, update, check_start, check_end),
+            Ordering::Greater => self.update_within(&left, range, update, check_start, check_end),
+            Ordering::Equal => {
+                update.apply(&mut node.borrow_mut().key.value);
+                self.update_within(&left, range, update, check_start, false);
+                self.update_within(&right, range, update, false, check_end);
+            }
+        }
+        self.tree.update(node);
+    }
+}
+
+impl<K, V, U> AggregateMap<K, V, Sum, U>
+where
+    K: PartialOrd + Clone + Debug + Default,
+    V: Add<Output = V> + Clone + Debug + Default,
+    U: Update<V, Sum>,
+{
+    /// Returns the sum of the values whose keys are within `range`, in
+    /// O(log n).
+    pub fn sum_range<R: RangeBounds<K>>(&self, range: R) -> V {
+        self.fold_range(range)
+    }
+}
+
+/// Returns the aggregate of the values below `node` whose keys are within
+/// `range`, with the updates `pending` above `node` applied. The start and
+/// end bounds are only checked if `check_start` and `check_end` are set,
+/// which is no longer needed once the descent has passed a key within the
+/// range on that side.
+///
+/// Below the node where the paths to both bounds split, one bound is checked
+/// per path and every node on it adds a whole subtree summary at most, so
+/// O(log n) nodes are visited.
+fn fold_within<K, V, A, U, R>(
+    node: &NodeRef<K, V, A, U>,
+    range: &R,
+    pending: Option<&U>,
+    check_start: bool,
+    check_end: bool,
+) -> A::Summary
+where
+    K: PartialOrd,
+    V: Clone,
+    A: Aggregate<V>,
+    U: Update<V, A>,
+    R: RangeBounds<K>,
+{
+    let node = node.borrow();
+    if node.is_nil() {
+        return A::identity();
+    }
+    if !check_start && !check_end {
+        let mut summary = node.key.summary.clone();
+        if let Some(update) = pending {
+            update.apply_to_summary(&mut summary, node.size);
+        }
+        return summary;
+    }
+
+    let below = then::<V, A, U>(node.key.pending.as_ref(), pending);
+    let below = below.as_ref();
+    match locate(&node.key.key, range, check_start, check_end) {
+        Ordering::Less => fold_within(node.right(), range, below, check_start, check_end),
+        Ordering::Greater => fold_within(node.left(), range, below, check_start, check_end),
+        Ordering::Equal => {
+            let left = fold_within(node.left(), range, below, check_start, false);
+            let right = fold_within(node.right(), range, below, false, check_end);
+            let mut value = node.key.value.clone();
+            if let Some(update) = pending {
+                update.apply(&mut value);
+            }
+            let middle = A::combine(&left, &A::summarize(&value));
+            A::combine(&middle, &right)
+        }
+    }
+}
+
+impl<K, V, A, U> Iter<'_, K, V, A, U>
+where
+    A: Aggregate<V>,
+    U: Update<V, A>,
+{
+    /// Pushes `node` and its left descendants, which have the updates
+    /// `pending` above `node` in common.
+    fn push_left(&mut self, mut node: NodeRef<K, V, A, U>, mut pending: Option<U>) {
+        while !node.borrow().is_nil() {
+            let below = then::<V, A, U>(node.borrow().key.pending.as_ref(), pending.as_ref());
+            let left = node.borrow().left().clone();
+            self.stack.push((node, pending));
+            node = left;
+            pending = below;
+        }
+    }
+}
+
+impl<'a, K, V, A, U> Iterator for Iter<'a, K, V, A, U>
+where
+    K: PartialOrd + Clone + Debug + Default,
+    V: Clone + Debug + Default,
+    A: Aggregate<V>,
+    U: Update<V, A>,
+{
+    type Item = (&'a K, V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (node, pending) = self.stack.pop()?;
+        let below = then::<V, A, U>(node.borrow().key.pending.as_ref(), pending.as_ref());
+        let right = node.borrow().right().clone();
+        self.push_left(right, below);
+
+        let entry = self.tree.key_ref(&node);
+        let mut value = entry.value.clone();
+        if let Some(update) = &pending {
+            update.apply(&mut value);
+        }
+        Some((&entry.key, value))
+    }
+}
+
+impl<K, V, A, U> Default for AggregateMap<K, V, A, U>
+where
+    K: PartialOrd + Clone + Debug + Default,
+    V: Clone + Debug + Default,
+    A: Aggregate<V>,
+    U: Update<V, A>,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V, A, U> FromIterator<(K, V)> for AggregateMap<K, V, A, U>
+where
+    K: PartialOrd + Clone + Debug + Default,
+    V: Clone + Debug + Default,
+    A: Aggregate<V>,
+    U: Update<V, A>,
+{
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut map = AggregateMap::new();
+        for (key, value) in iter {
+            map.insert(key, value);
+        }
+        map
+    }
+}
+
+impl<K, V, A, U> Debug for AggregateMap<K, V, A, U>
+where
+    K: PartialOrd + Clone + Debug + Default,
+    V: Clone + Debug + Default,
+    A: Aggregate<V>,
+    U: Update<V, A>,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
@@ -0,0 +1,257 @@
+use crate::aggregate::{Aggregate, AggregateMap, Offset, Sum, Update};
+use proptest::prelude::*;
+use std::{collections::BTreeMap, format, vec::Vec};
+
+/// The largest value, which unlike a sum cannot be computed from prefix
+/// aggregates.
+struct Max;
+
+impl Aggregate<i64> for Max {
+    type Summary = Option<i64>;
+
+    fn identity() -> Option<i64> {
+        None
+    }
+
+    fn summarize(value: &i64) -> Option<i64> {
+        Some(*value)
+    }
+
+    fn combine(left: &Option<i64>, right: &Option<i64>) -> Option<i64> {
+        (*left).max(*right)
+    }
+}
+
+/// The values in key order, which only comes out right if every summary
+/// combines its parts in order.
+struct Concat;
+
+impl Aggregate<char> for Concat {
+    type Summary = Vec<char>;
+
+    fn identity() -> Vec<char> {
+        Vec::new()
+    }
+
+    fn summarize(value: &char) -> Vec<char> {
+        std::vec![*value]
+    }
+
+    fn combine(left: &Vec<char>, right: &Vec<char>) -> Vec<char> {
+        left.iter().chain(right).copied().collect()
+    }
+}
+
+/// Adds to every value, which moves their maximum along.
+#[derive(Clone, Debug)]
+struct Raise(i64);
+
+impl Update<i64, Max> for Raise {
+    fn apply(&self, value: &mut i64) {
+        *value += self.0;
+    }
+
+    fn apply_to_summary(&self, summary: &mut Option<i64>, _: usize) {
+        if let Some(max) = summary {
+            *max += self.0;
+        }
+    }
+
+    fn then(&self, later: &Self) -> Self {
+        Raise(self.0 + later.0)
+    }
+}
+
+#[test]
+fn test_sum_range() {
+    let map: AggregateMap<i32, i64> = (0..100).map(|key| (key, key as i64)).collect();
+
+    assert_eq!(map.sum_range(10..20), (10..20).sum());
+    assert_eq!(map.sum_range(10..=20), (10..=20).sum());
+    assert_eq!(map.sum_range(..), (0..100).sum());
+    assert_eq!(map.sum_range(200..), 0);
+    assert_eq!(map.total(), (0..100).sum());
+}
+
+#[test]
+fn test_count_range() {
+    let map: AggregateMap<i32, i64> = (0..100).map(|key| (key * 2, 1)).collect();
+
+    assert_eq!(map.count_range(10..20), 5);
+    assert_eq!(map.count_range(..), 100);
+}
+
+#[test]
+fn test_summaries_follow_updates() {
+    let mut map: AggregateMap<i32, i64> = (0..10).map(|key| (key, 1)).collect();
+
+    assert_eq!(map.insert(3, 10), Some(1));
+    assert!(map.update(&4, |value| *value += 5));
+    assert!(!map.update(&40, |value| *value += 5));
+    assert_eq!(map.remove(&0), Some(1));
+
+    assert_eq!(map.sum_range(..), 7 + 10 + 6);
+    assert_eq!(map.sum_range(3..5), 16);
+}
+
+#[test]
+fn test_custom_aggregate() {
+    let map: AggregateMap<i32, i64, Max> = [(1, 5), (2, -3), (3, 9), (4, 0)].into_iter().collect();
+
+    assert_eq!(map.fold_range(1..3), Some(5));
+    assert_eq!(map.fold_range(2..), Some(9));
+    assert_eq!(map.fold_range(5..), None);
+}
+
+#[test]
+fn test_aggregate_keeps_order() {
+    let mut map: AggregateMap<i32, char, Concat> = AggregateMap::new();
+    for (key, value) in (0..26).rev().zip('a'..='z') {
+        map.insert(key, value);
+    }
+
+    let expected: Vec<_> = ('a'..='z').rev().collect();
+    assert_eq!(map.total(), expected);
+    assert_eq!(map.fold_range(20..23), ['f', 'e', 'd']);
+}
+
+#[test]
+fn test_update_range() {
+    let mut map: AggregateMap<i32, i64, Sum, Offset<i64>> = (0..100).map(|key| (key, 1)).collect();
+
+    map.update_range(10..20, Offset(2));
+    map.update_range(15..=30, Offset(-1));
+
+    assert_eq!(map.get(&9), Some(1));
+    assert_eq!(map.get(&10), Some(3));
+    assert_eq!(map.get(&15), Some(2));
+    assert_eq!(map.get(&30), Some(0));
+    assert_eq!(map.get(&31), Some(1));
+    assert_eq!(map.sum_range(..), 100 + 20 - 16);
+    assert_eq!(map.total(), 100 + 20 - 16);
+    assert_eq!(map.sum_range(12..17), 3 + 3 + 3 + 2 + 2);
+    assert!(map.iter().map(|(_, value)| value).eq((0..100)
+        .map(|key| 1 + 2 * (10..20).contains(&key) as i64 - (15..=30).contains(&key) as i64)));
+    assert_eq!(map.tree.validate(), Ok(()));
+}
+
+#[test]
+fn test_pending_updates_survive_restructuring() {
+    let mut map: AggregateMap<i32, i64, Sum, Offset<i64>> = AggregateMap::new();
+    for key in 0..64 {
+        map.insert(key * 2, 0);
+    }
+    map.update_range(.., Offset(1));
+    // Rotations and removals pass the pending offset on before they move
+    // nodes away from the roots that hold it.
+    for key in 0..64 {
+        map.insert(key * 2 + 1, 0);
+    }
+    for key in 0..32 {
+        map.remove(&(key * 4));
+    }
+    assert_eq!(map.insert(2, 10), Some(1));
+    assert!(map.update(&6, |value| *value *= 5));
+
+    assert_eq!(map.sum_range(..), 30 + 10 + 5);
+    assert_eq!(map.total(), 30 + 10 + 5);
+    assert_eq!(map.get(&2), Some(10));
+    assert_eq!(map.get(&6), Some(5));
+    assert_eq!(map.get(&1), Some(0));
+    assert_eq!(map.tree.validate(), Ok(()));
+}
+
+#[test]
+fn test_custom_update() {
+    let mut map: AggregateMap<i32, i64, Max, Raise> =
+        (0..10).map(|key| (key, key as i64)).collect();
+
+    map.update_range(..5, Raise(10));
+
+    assert_eq!(map.fold_range(..), Some(14));
+    assert_eq!(map.fold_range(5..), Some(9));
+    assert_eq!(map.get(&0), Some(10));
+}
+
+#[test]
+fn test_debug() {
+    let map: AggregateMap<i32, i64, Sum> = [(2, 20), (1, 10)].into_iter().collect();
+
+    assert_eq!(format!("{:?}", map), "{1: 10, 2: 20}");
+}
+
+#[test]
+fn test_incomparable_key_matches_no_other_key() {
+    let mut map: AggregateMap<f64, i64> = AggregateMap::new();
+    map.insert(1.0, 1);
+
+    assert_eq!(map.insert(f64::NAN, 2), None);
+
+    assert_eq!(map.len(), 2);
+    assert_eq!(map.get(&1.0), Some(1));
+    assert_eq!(map.get(&f64::NAN), None);
+    assert_eq!(map.total(), 3);
+}
+
+proptest! {
+    #[test]
+    fn test_matches_btree_map(
+        ops in prop::collection::vec((0u8..64, any::<i16>(), any::<bool>()), 0..200),
+        start in 0u8..70,
+        end in 0u8..70,
+    ) {
+        let mut map = AggregateMap::<u8, i64>::new();
+        let mut model = BTreeMap::new();
+        for (key, value, insert) in ops {
+            if insert {
+                prop_assert_eq!(map.insert(key, value as i64), model.insert(key, value as i64));
+            } else {
+                prop_assert_eq!(map.remove(&key), model.remove(&key));
+            }
+            prop_assert_eq!(map.tree.validate(), Ok(()));
+        }
+        if start <= end {
+            prop_assert_eq!(map.sum_range(start..end), model.range(start..end).map(|(_, v)| v).sum::<i64>());
+            prop_assert_eq!(map.count_range(start..end), model.range(start..end).count());
+        }
+        prop_assert_eq!(map.sum_range(start..), model.range(start..).map(|(_, v)| v).sum::<i64>());
+        prop_assert_eq!(map.sum_range(..=end), model.range(..=end).map(|(_, v)| v).sum::<i64>());
+    }
+
+    #[test]
+    fn test_update_range_matches_btree_map(
+        ops in prop::collection::vec((0u8..64, 0u8..64, any::<i16>(), 0u8..3), 0..200),
+    ) {
+        let mut map = AggregateMap::<u8, i64, Sum, Offset<i64>>::new();
+        let mut model = BTreeMap::new();
+        for (key, end, value, op) in ops {
+            match op {
+                0 => {
+                    prop_assert_eq!(map.insert(key, value as i64), model.insert(key, value as i64));
+                }
+                1 => {
+                    prop_assert_eq!(map.remove(&key), model.remove(&key));
+                }
+                _ => {
+                    map.update_range(key..=end, Offset(value as i64));
+                    for (_, model_value) in model.range_mut(key..=end.max(key)).filter(|_| key <= end) {
+                        *model_value += value as i64;
+                    }
+                }
+            }
+            prop_assert_eq!(map.tree.validate(), Ok(()));
+            let start = key.min(end);
+            prop_assert_eq!(map.sum_range(start..end.max(key)), model.range(start..end.max(key)).map(|(_, v)| v).sum::<i64>());
+        }
+        prop_assert_eq!(map.total(), model.values().sum::<i64>());
+        prop_assert!(map.iter().map(|(key, value)| (*key, value)).eq(model.into_iter()));
+    }
+}
+
+#[test]
+#[should_panic(expected = "Subtree size does not fit the offset type")]
+fn test_offset_does_not_truncate_subtree_size() {
+    let mut summary = 0i8;
+
+    Update::<i8, Sum>::apply_to_summary(&Offset(1), &mut summary, 200);
+}
@@ -0,0 +1,16 @@
+//! Alternative balancing strategies behind the same [`OrderedSet`] interface
+//! as the red-black [`Tree`], for teaching and benchmarking. They share the
+//! node representation and rotation primitives of the red-black tree.
+//!
+//! [`OrderedSet`]: crate::ordered_set::OrderedSet
+//! [`Tree`]: crate::tree::Tree
+
+mod avl;
+mod llrb;
+mod scapegoat;
+mod treap;
+
+pub use avl::AvlTree;
+pub use llrb::LlrbTree;
+pub use scapegoat::ScapegoatTree;
+pub use treap::Treap;
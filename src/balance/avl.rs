@@ -0,0 +1,146 @@
+use crate::{bst, node::Node, ordered_set::OrderedSet};
+use alloc::rc::Rc;
+use core::{cell::RefCell, fmt::Debug};
+
+#[cfg(test)]
+mod avl_tests;
+
+/// An AVL tree: the heights of the two subtrees of every node differ by at
+/// most one. Every node caches the height of its subtree, with T.nil at
+/// height 0.
+pub struct AvlTree<T> {
+    root: Rc<RefCell<Node<T>>>,
+    sentinel: Rc<RefCell<Node<T>>>,
+    length: usize,
+}
+
+impl<T: PartialOrd + Clone + PartialEq + Debug + Default> AvlTree<T> {
+    pub fn new() -> AvlTree<T> {
+        let sentinel = Rc::new(RefCell::new(Node::new_sentinel()));
+        Self {
+            root: sentinel.clone(),
+            sentinel,
+            length: 0,
+        }
+    }
+
+    /// Returns the height of the tree, or 0 if it is empty.
+    pub fn height(&self) -> u64 {
+        self.root.borrow().rank
+    }
+
+    /// Walks from `node` up to the root, updating heights and rotating every
+    /// node whose subtrees differ in height by two.
+    fn rebalance(&mut self, mut node: Rc<RefCell<Node<T>>>) {
+        while !node.borrow().is_nil() {
+            update_height(&node);
+            let left = node.borrow().left().clone();
+            let right = node.borrow().right().clone();
+            let balance = height(&left) as i64 - height(&right) as i64;
+
+            if balance > 1 {
+                if height(left.borrow().left()) < height(left.borrow().right()) {
+                    self.left_rotate(left);
+                }
+                self.right_rotate(node.clone());
+            } else if balance < -1 {
+                if height(right.borrow().right()) < height(right.borrow().left()) {
+                    self.right_rotate(right);
+                }
+                self.left_rotate(node.clone());
+            }
+
+            // After a rotation `node` moved down, and its parent is the new
+            // root of the subtree that has just been fixed.
+            let node_tmp = if balance.abs() > 1 {
+                node.borrow().parent().borrow().parent().clone()
+            } else {
+                node.borrow().parent().clone()
+            };
+            node = node_tmp;
+        }
+    }
+
+    fn left_rotate(&mut self, x: Rc<RefCell<Node<T>>>) {
+        bst::left_rotate(&mut self.root, x.clone());
+        update_height(&x);
+        update_height(x.borrow().parent());
+    }
+
+    fn right_rotate(&mut self, y: Rc<RefCell<Node<T>>>) {
+        bst::right_rotate(&mut self.root, y.clone());
+        update_height(&y);
+        update_height(y.borrow().parent());
+    }
+}
+
+impl<T: PartialOrd + Clone + PartialEq + Debug + Default> OrderedSet<T> for AvlTree<T> {
+    fn insert(&mut self, key: T) -> bool {
+        let mut z = Node::new(key);
+        z.set_left_child(self.sentinel.clone());
+        z.set_right_child(self.sentinel.clone());
+        z.rank = 1;
+        let z = Rc::new(RefCell::new(z));
+        if !bst::attach(&mut self.root, &self.sentinel, z.clone()) {
+            return false;
+        }
+        let parent = z.borrow().parent().clone();
+        self.rebalance(parent);
+        self.length += 1;
+        true
+    }
+
+    fn delete(&mut self, key: T) {
+        if let Some(z) = bst::search(&self.root, &key) {
+            let start = bst::remove(&mut self.root, z);
+            self.rebalance(start);
+            self.length -= 1;
+        }
+    }
+
+    fn contains_key(&self, key: T) -> bool {
+        bst::search(&self.root, &key).is_some()
+    }
+
+    fn minimum(&self) -> Option<T> {
+        bst::minimum(&self.root)
+    }
+
+    fn maximum(&self) -> Option<T> {
+        bst::maximum(&self.root)
+    }
+
+    fn len(&self) -> usize {
+        self.length
+    }
+
+    fn clear(&mut self) {
+        bst::clear(&mut self.root, &self.sentinel);
+        self.length = 0;
+    }
+}
+
+impl<T: PartialOrd + Clone + PartialEq + Debug + Default> Default for AvlTree<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Drop for AvlTree<T> {
+    fn drop(&mut self) {
+        bst::clear(&mut self.root, &self.sentinel);
+    }
+}
+
+fn height<T>(node: &Rc<RefCell<Node<T>>>) -> u64 {
+    node.borrow().rank
+}
+
+fn update_height<T>(node: &Rc<RefCell<Node<T>>>) {
+    if node.borrow().is_nil() {
+        return;
+    }
+    let left = height(node.borrow().left());
+    let right = height(node.borrow().right());
+    node.borrow_mut().rank = 1 + left.max(right);
+}
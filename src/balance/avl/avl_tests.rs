@@ -0,0 +1,70 @@
+use crate::{balance::AvlTree, node::Node, ordered_set::OrderedSet};
+use proptest::prelude::*;
+use std::{cell::RefCell, rc::Rc, vec::Vec};
+
+/// Checks the AVL balance condition and the cached heights below `node`,
+/// returning the height of the subtree.
+fn check_avl<T>(node: &Rc<RefCell<Node<T>>>) -> u64 {
+    if node.borrow().is_nil() {
+        return 0;
+    }
+    let left = check_avl(node.borrow().left());
+    let right = check_avl(node.borrow().right());
+    assert!(left.abs_diff(right) <= 1, "AVL balance violated");
+    let height = 1 + left.max(right);
+    assert_eq!(node.borrow().rank, height, "Cached height is stale");
+    height
+}
+
+#[test]
+fn test_sequential_inserts_stay_balanced() {
+    let mut tree = AvlTree::new();
+    for key in 0..1023 {
+        tree.insert(key);
+    }
+
+    check_avl(&tree.root);
+    assert_eq!(tree.height(), 10);
+}
+
+#[test]
+fn test_deletes_stay_balanced() {
+    let mut tree = AvlTree::new();
+    for key in 0..100 {
+        tree.insert(key);
+    }
+    for key in (0..100).step_by(3) {
+        tree.delete(key);
+        check_avl(&tree.root);
+    }
+
+    assert_eq!(tree.len(), 66);
+}
+
+#[test]
+fn test_delete_unlinks_sentinel_parent() {
+    let mut tree = AvlTree::new();
+    for key in 0..20 {
+        tree.insert(key);
+    }
+    for key in (0..20).rev() {
+        tree.delete(key);
+        assert!(tree.sentinel.borrow().parent.is_none());
+    }
+}
+
+proptest! {
+    #[test]
+    fn test_random_operations_stay_balanced(keys in prop::collection::vec(any::<u8>(), 0..200)) {
+        let mut tree = AvlTree::new();
+        for key in &keys {
+            tree.insert(*key);
+            check_avl(&tree.root);
+        }
+        let removals: Vec<u8> = keys.iter().step_by(2).copied().collect();
+        for key in removals {
+            tree.delete(key);
+            check_avl(&tree.root);
+        }
+    }
+}
@@ -0,0 +1,247 @@
+use crate::{
+    bst,
+    node::{Node, NodeColor},
+    ordered_set::OrderedSet,
+};
+use alloc::rc::Rc;
+use core::{cell::RefCell, fmt::Debug};
+
+#[cfg(test)]
+mod llrb_tests;
+
+/// Sedgewick's left-leaning red-black tree, the 2-3 tree variant in which red
+/// links always lean left. Insertion fixes the tree bottom-up along the parent
+/// links, deletion uses the recursive top-down formulation.
+pub struct LlrbTree<T> {
+    root: Rc<RefCell<Node<T>>>,
+    sentinel: Rc<RefCell<Node<T>>>,
+    length: usize,
+}
+
+impl<T: PartialOrd + Clone + PartialEq + Debug + Default> LlrbTree<T> {
+    pub fn new() -> LlrbTree<T> {
+        let sentinel = Rc::new(RefCell::new(Node::new_sentinel()));
+        Self {
+            root: sentinel.clone(),
+            sentinel,
+            length: 0,
+        }
+    }
+
+    /// Rotates the red right link of `h` to the left and returns the new root
+    /// of the subtree.
+    fn rotate_left(&mut self, h: Rc<RefCell<Node<T>>>) -> Rc<RefCell<Node<T>>> {
+        let x = h.borrow().right().clone();
+        bst::left_rotate(&mut self.root, h.clone());
+        x.borrow_mut().color = h.borrow().color.clone();
+        h.borrow_mut().color = NodeColor::Red;
+        x
+    }
+
+    /// Rotates the red left link of `h` to the right and returns the new root
+    /// of the subtree.
+    fn rotate_right(&mut self, h: Rc<RefCell<Node<T>>>) -> Rc<RefCell<Node<T>>> {
+        let x = h.borrow().left().clone();
+        bst::right_rotate(&mut self.root, h.clone());
+        x.borrow_mut().color = h.borrow().color.clone();
+        h.borrow_mut().color = NodeColor::Red;
+        x
+    }
+
+    /// Restores the left-leaning invariants at `h` and returns the new root of
+    /// the subtree.
+    fn balance(&mut self, mut h: Rc<RefCell<Node<T>>>) -> Rc<RefCell<Node<T>>> {
+        if is_red(h.borrow().right()) && !is_red(h.borrow().left()) {
+            h = self.rotate_left(h);
+        }
+        if is_red(h.borrow().left()) && is_red_left_left(&h) {
+            h = self.rotate_right(h);
+        }
+        if is_red(h.borrow().left()) && is_red(h.borrow().right()) {
+            flip_colors(&h);
+        }
+        h
+    }
+
+    /// Makes the left child of `h` or one of its children red, assuming `h`
+    /// is red and both `h.left` and `h.left.left` are black.
+    fn move_red_left(&mut self, mut h: Rc<RefCell<Node<T>>>) -> Rc<RefCell<Node<T>>> {
+        flip_colors(&h);
+        if is_red_left_left_of(h.borrow().right()) {
+            let right = h.borrow().right().clone();
+            self.rotate_right(right);
+            h = self.rotate_left(h);
+            flip_colors(&h);
+        }
+        h
+    }
+
+    /// Makes the right child of `h` or one of its children red, assuming `h`
+    /// is red and both `h.right` and `h.right.left` are black.
+    fn move_red_right(&mut self, mut h: Rc<RefCell<Node<T>>>) -> Rc<RefCell<Node<T>>> {
+        flip_colors(&h);
+        if is_red_left_left(&h) {
+            h = self.rotate_right(h);
+            flip_colors(&h);
+        }
+        h
+    }
+
+    /// Deletes the smallest key below `h`.
+    fn delete_min(&mut self, mut h: Rc<RefCell<Node<T>>>) {
+        if h.borrow().left().borrow().is_nil() {
+            let sentinel = self.sentinel.clone();
+            bst::transplant(&mut self.root, h, sentinel);
+            return;
+        }
+        if !is_red(h.borrow().left()) && !is_red_left_left(&h) {
+            h = self.move_red_left(h);
+        }
+        let left = h.borrow().left().clone();
+        self.delete_min(left);
+        self.balance(h);
+    }
+
+    /// Deletes `key` from the subtree rooted at `h`, which must contain it.
+    fn delete_from(&mut self, mut h: Rc<RefCell<Node<T>>>, key: &T) {
+        if *key < h.borrow().key {
+            if !is_red(h.borrow().left()) && !is_red_left_left(&h) {
+                h = self.move_red_left(h);
+            }
+            let left = h.borrow().left().clone();
+            self.delete_from(left, key);
+        } else {
+            if is_red(h.borrow().left()) {
+                h = self.rotate_right(h);
+            }
+            if *key == h.borrow().key && h.borrow().right().borrow().is_nil() {
+                let sentinel = self.sentinel.clone();
+                bst::transplant(&mut self.root, h, sentinel);
+                return;
+            }
+            if !is_red(h.borrow().right()) && !is_red_left_left_of(h.borrow().right()) {
+                h = self.move_red_right(h);
+            }
+            let right = h.borrow().right().clone();
+            if *key == h.borrow().key {
+                // Replace the key with its successor, then delete the successor.
+                let successor = bst::minimum_node(right.clone()).borrow().key.clone();
+                h.borrow_mut().key = successor;
+                self.delete_min(right);
+            } else {
+                self.delete_from(right, key);
+            }
+        }
+        self.balance(h);
+    }
+}
+
+impl<T: PartialOrd + Clone + PartialEq + Debug + Default> OrderedSet<T> for LlrbTree<T> {
+    fn insert(&mut self, key: T) -> bool {
+        let mut z = Node::new(key);
+        z.set_left_child(self.sentinel.clone());
+        z.set_right_child(self.sentinel.clone());
+        z.color = NodeColor::Red;
+        let z = Rc::new(RefCell::new(z));
+        if !bst::attach(&mut self.root, &self.sentinel, z.clone()) {
+            return false;
+        }
+
+        // Apply the fixes of the recursive insertion on the way back up.
+        let mut h = z.borrow().parent().clone();
+        while !h.borrow().is_nil() {
+            h = self.balance(h);
+            let h_tmp = h.borrow().parent().clone();
+            h = h_tmp;
+        }
+        self.root.borrow_mut().color = NodeColor::Black;
+        self.length += 1;
+        true
+    }
+
+    fn delete(&mut self, key: T) {
+        if bst::search(&self.root, &key).is_none() {
+            return;
+        }
+
+        if !is_red(self.root.borrow().left()) && !is_red(self.root.borrow().right()) {
+            self.root.borrow_mut().color = NodeColor::Red;
+        }
+        self.delete_from(self.root.clone(), &key);
+        // Splicing out the node transplants T.nil, which points its parent at
+        // a node of the tree. Drop that link again.
+        self.sentinel.borrow_mut().parent = None;
+        if !self.root.borrow().is_nil() {
+            self.root.borrow_mut().color = NodeColor::Black;
+        }
+        self.length -= 1;
+    }
+
+    fn contains_key(&self, key: T) -> bool {
+        bst::search(&self.root, &key).is_some()
+    }
+
+    fn minimum(&self) -> Option<T> {
+        bst::minimum(&self.root)
+    }
+
+    fn maximum(&self) -> Option<T> {
+        bst::maximum(&self.root)
+    }
+
+    fn len(&self) -> usize {
+        self.length
+    }
+
+    fn clear(&mut self) {
+        bst::clear(&mut self.root, &self.sentinel);
+        self.length = 0;
+    }
+}
+
+impl<T: PartialOrd + Clone + PartialEq + Debug + Default> Default for LlrbTree<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Drop for LlrbTree<T> {
+    fn drop(&mut self) {
+        bst::clear(&mut self.root, &self.sentinel);
+    }
+}
+
+/// T.nil is black.
+fn is_red<T>(node: &Rc<RefCell<Node<T>>>) -> bool {
+    node.borrow().color == NodeColor::Red
+}
+
+fn is_red_left_left<T>(h: &Rc<RefCell<Node<T>>>) -> bool {
+    is_red_left_left_of(h.borrow().left())
+}
+
+/// Whether the left child of `node` is red. T.nil has no children.
+fn is_red_left_left_of<T>(node: &Rc<RefCell<Node<T>>>) -> bool {
+    let node = node.borrow();
+    !node.is_nil() && is_red(node.left())
+}
+
+/// Toggles the colors of `h` and its children, splitting or merging a 4-node.
+fn flip_colors<T>(h: &Rc<RefCell<Node<T>>>) {
+    let color = toggle(&h.borrow().color);
+    h.borrow_mut().color = color;
+    let h = h.borrow();
+    for node in [h.left(), h.right()] {
+        let mut node = node.borrow_mut();
+        if !node.is_nil() {
+            node.color = toggle(&node.color);
+        }
+    }
+}
+
+fn toggle(color: &NodeColor) -> NodeColor {
+    match color {
+        NodeColor::Red => NodeColor::Black,
+        NodeColor::Black => NodeColor::Red,
+    }
+}
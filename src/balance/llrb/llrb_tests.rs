@@ -0,0 +1,101 @@
+use crate::{
+    balance::LlrbTree,
+    node::{Node, NodeColor},
+    ordered_set::OrderedSet,
+};
+use proptest::prelude::*;
+use std::{cell::RefCell, rc::Rc, vec::Vec};
+
+fn is_red<T>(node: &Rc<RefCell<Node<T>>>) -> bool {
+    node.borrow().color == NodeColor::Red
+}
+
+/// Checks that red links lean left, that no two red links follow each other
+/// and that every path has the same number of black links. Returns the black
+/// height of the subtree.
+fn check_llrb<T>(node: &Rc<RefCell<Node<T>>>) -> usize {
+    if node.borrow().is_nil() {
+        return 1;
+    }
+    let node = node.borrow();
+    assert!(!is_red(node.right()), "Red link leans right");
+    if is_red(node.left()) {
+        assert!(node.color == NodeColor::Black, "Two red links in a row");
+    }
+    let left = check_llrb(node.left());
+    let right = check_llrb(node.right());
+    assert_eq!(left, right, "Black height differs");
+    left + usize::from(node.color == NodeColor::Black)
+}
+
+fn assert_llrb<T>(tree: &LlrbTree<T>) {
+    assert!(!is_red(&tree.root), "Root must be black");
+    check_llrb(&tree.root);
+}
+
+#[test]
+fn test_sequential_inserts_lean_left() {
+    let mut tree = LlrbTree::new();
+    for key in 0..500 {
+        tree.insert(key);
+        assert_llrb(&tree);
+    }
+}
+
+#[test]
+fn test_delete_every_key() {
+    let mut tree = LlrbTree::new();
+    for key in 0..100 {
+        tree.insert((key * 37) % 100);
+    }
+    for key in 0..100 {
+        tree.delete(key);
+        assert_llrb(&tree);
+        assert!(!tree.contains_key(key));
+    }
+
+    assert!(tree.is_empty());
+}
+
+#[test]
+fn test_delete_unlinks_sentinel_parent() {
+    let mut tree = LlrbTree::new();
+    for key in 0..20 {
+        tree.insert(key);
+    }
+    for key in (0..20).rev() {
+        tree.delete(key);
+        assert!(tree.sentinel.borrow().parent.is_none());
+    }
+}
+
+proptest! {
+    #[test]
+    fn test_random_operations_keep_invariants(keys in prop::collection::vec(any::<u8>(), 0..200)) {
+        let mut tree = LlrbTree::new();
+        for key in &keys {
+            tree.insert(*key);
+            assert_llrb(&tree);
+        }
+        let removals: Vec<u8> = keys.iter().step_by(2).copied().collect();
+        for key in removals {
+            tree.delete(key);
+            assert_llrb(&tree);
+        }
+    }
+}
+
+#[test]
+fn test_duplicates_are_rejected() {
+    let mut tree = LlrbTree::new();
+    for key in [0, 244, 0, 254, 254, 244, 0] {
+        tree.insert(key);
+        assert_llrb(&tree);
+    }
+
+    assert!(!tree.insert(254));
+    assert_eq!(tree.len(), 3);
+    tree.delete(254);
+    assert_llrb(&tree);
+    assert!(!tree.contains_key(254));
+}
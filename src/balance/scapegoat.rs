@@ -0,0 +1,202 @@
+use crate::{bst, node::Node, ordered_set::OrderedSet};
+use alloc::{rc::Rc, vec, vec::Vec};
+use core::{cell::RefCell, fmt::Debug};
+
+#[cfg(test)]
+mod scapegoat_tests;
+
+/// A scapegoat tree with balance factor α = 2/3.
+///
+/// Nodes carry no balancing metadata. When an insertion lands deeper than
+/// `log_{3/2}(n)`, the closest ancestor whose child holds more than 2/3 of
+/// its subtree is rebuilt into a perfectly balanced subtree. Deletions
+/// rebuild the whole tree once it has shrunk below 2/3 of its largest size.
+pub struct ScapegoatTree<T> {
+    root: Rc<RefCell<Node<T>>>,
+    sentinel: Rc<RefCell<Node<T>>>,
+    length: usize,
+    max_length: usize,
+}
+
+impl<T: PartialOrd + Clone + PartialEq + Debug + Default> ScapegoatTree<T> {
+    pub fn new() -> ScapegoatTree<T> {
+        let sentinel = Rc::new(RefCell::new(Node::new_sentinel()));
+        Self {
+            root: sentinel.clone(),
+            sentinel,
+            length: 0,
+            max_length: 0,
+        }
+    }
+
+    /// Climbs from the freshly inserted `z` to the first ancestor that is not
+    /// α-weight-balanced and rebuilds it.
+    fn rebuild_scapegoat(&mut self, z: Rc<RefCell<Node<T>>>) {
+        let mut x = z;
+        let mut x_size = 1;
+        loop {
+            let parent = x.borrow().parent().clone();
+            if parent.borrow().is_nil() {
+                return;
+            }
+            let sibling = if Rc::ptr_eq(&x, parent.borrow().left()) {
+                parent.borrow().right().clone()
+            } else {
+                parent.borrow().left().clone()
+            };
+            let parent_size = x_size + subtree_size(&sibling) + 1;
+            if 3 * x_size > 2 * parent_size {
+                self.rebuild(parent);
+                return;
+            }
+            x = parent;
+            x_size = parent_size;
+        }
+    }
+
+    /// Replaces the subtree rooted at `node` with a perfectly balanced one
+    /// holding the same nodes.
+    fn rebuild(&mut self, node: Rc<RefCell<Node<T>>>) {
+        let parent = node.borrow().parent().clone();
+        let is_left = !parent.borrow().is_nil() && Rc::ptr_eq(&node, parent.borrow().left());
+
+        let mut nodes = vec![];
+        flatten(node, &mut nodes);
+        let subtree = self.build(&nodes, &parent);
+
+        if parent.borrow().is_nil() {
+            self.root = subtree;
+        } else if is_left {
+            parent.borrow_mut().set_left_child(subtree);
+        } else {
+            parent.borrow_mut().set_right_child(subtree);
+        }
+    }
+
+    /// Links the sorted `nodes` into a balanced subtree below `parent` and
+    /// returns its root.
+    fn build(
+        &self,
+        nodes: &[Rc<RefCell<Node<T>>>],
+        parent: &Rc<RefCell<Node<T>>>,
+    ) -> Rc<RefCell<Node<T>>> {
+        if nodes.is_empty() {
+            return self.sentinel.clone();
+        }
+        let middle = nodes.len() / 2;
+        let node = nodes[middle].clone();
+        let left = self.build(&nodes[..middle], &node);
+        let right = self.build(&nodes[middle + 1..], &node);
+        let mut node_mut = node.borrow_mut();
+        node_mut.set_left_child(left);
+        node_mut.set_right_child(right);
+        node_mut.set_parent(parent.clone());
+        drop(node_mut);
+        node
+    }
+}
+
+impl<T: PartialOrd + Clone + PartialEq + Debug + Default> OrderedSet<T> for ScapegoatTree<T> {
+    fn insert(&mut self, key: T) -> bool {
+        let mut z = Node::new(key);
+        z.set_left_child(self.sentinel.clone());
+        z.set_right_child(self.sentinel.clone());
+        let z = Rc::new(RefCell::new(z));
+        if !bst::attach(&mut self.root, &self.sentinel, z.clone()) {
+            return false;
+        }
+        self.length += 1;
+        self.max_length = self.max_length.max(self.length);
+
+        if depth(&z) > height_limit(self.length) {
+            self.rebuild_scapegoat(z);
+        }
+        true
+    }
+
+    fn delete(&mut self, key: T) {
+        if let Some(z) = bst::search(&self.root, &key) {
+            bst::remove(&mut self.root, z);
+            self.length -= 1;
+            if 3 * self.length < 2 * self.max_length {
+                if !self.root.borrow().is_nil() {
+                    self.rebuild(self.root.clone());
+                }
+                self.max_length = self.length;
+            }
+        }
+    }
+
+    fn contains_key(&self, key: T) -> bool {
+        bst::search(&self.root, &key).is_some()
+    }
+
+    fn minimum(&self) -> Option<T> {
+        bst::minimum(&self.root)
+    }
+
+    fn maximum(&self) -> Option<T> {
+        bst::maximum(&self.root)
+    }
+
+    fn len(&self) -> usize {
+        self.length
+    }
+
+    fn clear(&mut self) {
+        bst::clear(&mut self.root, &self.sentinel);
+        self.length = 0;
+        self.max_length = 0;
+    }
+}
+
+impl<T: PartialOrd + Clone + PartialEq + Debug + Default> Default for ScapegoatTree<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Drop for ScapegoatTree<T> {
+    fn drop(&mut self) {
+        bst::clear(&mut self.root, &self.sentinel);
+    }
+}
+
+/// The deepest a node may be in a tree of `n` nodes: `floor(log_{3/2}(n))`.
+fn height_limit(n: usize) -> usize {
+    let mut limit = 0;
+    let mut bound = 1.5_f64;
+    while bound <= n as f64 {
+        limit += 1;
+        bound *= 1.5;
+    }
+    limit
+}
+
+fn depth<T>(node: &Rc<RefCell<Node<T>>>) -> usize {
+    let mut depth = 0;
+    let mut node = node.borrow().parent().clone();
+    while !node.borrow().is_nil() {
+        depth += 1;
+        let node_tmp = node.borrow().parent().clone();
+        node = node_tmp;
+    }
+    depth
+}
+
+fn subtree_size<T>(node: &Rc<RefCell<Node<T>>>) -> usize {
+    if node.borrow().is_nil() {
+        return 0;
+    }
+    1 + subtree_size(node.borrow().left()) + subtree_size(node.borrow().right())
+}
+
+/// Appends the nodes below `node` to `nodes` in sorted order.
+fn flatten<T>(node: Rc<RefCell<Node<T>>>, nodes: &mut Vec<Rc<RefCell<Node<T>>>>) {
+    if node.borrow().is_nil() {
+        return;
+    }
+    flatten(node.borrow().left().clone(), nodes);
+    nodes.push(node.clone());
+    flatten(node.borrow().right().clone(), nodes);
+}
@@ -0,0 +1,74 @@
+use crate::{balance::ScapegoatTree, node::Node, ordered_set::OrderedSet};
+use proptest::prelude::*;
+use std::{cell::RefCell, format, rc::Rc, vec::Vec};
+
+fn height<T>(node: &Rc<RefCell<Node<T>>>) -> usize {
+    if node.borrow().is_nil() {
+        return 0;
+    }
+    1 + height(node.borrow().left()).max(height(node.borrow().right()))
+}
+
+/// `floor(log_{3/2}(n)) + 1`, the number of levels a scapegoat tree of `n`
+/// nodes may have after an insertion.
+fn max_height(n: usize) -> usize {
+    let mut levels = 1;
+    let mut bound = 1.5_f64;
+    while bound <= n as f64 {
+        levels += 1;
+        bound *= 1.5;
+    }
+    levels
+}
+
+#[test]
+fn test_sequential_inserts_rebuild() {
+    let mut tree = ScapegoatTree::new();
+    for key in 0..1000 {
+        tree.insert(key);
+        assert!(height(&tree.root) <= max_height(tree.len()));
+    }
+}
+
+#[test]
+fn test_deletes_rebuild_the_whole_tree() {
+    let mut tree = ScapegoatTree::new();
+    for key in 0..90 {
+        tree.insert(key);
+    }
+    for key in 0..31 {
+        tree.delete(key);
+    }
+
+    // Dropping below 2/3 of the largest size rebuilt a perfectly balanced tree.
+    assert_eq!(tree.max_length, 59);
+    assert_eq!(height(&tree.root), 6);
+}
+
+#[test]
+fn test_delete_unlinks_sentinel_parent() {
+    let mut tree = ScapegoatTree::new();
+    for key in 0..20 {
+        tree.insert(key);
+    }
+    for key in (0..20).rev() {
+        tree.delete(key);
+        assert!(tree.sentinel.borrow().parent.is_none());
+    }
+}
+
+proptest! {
+    #[test]
+    fn test_inserts_keep_height_bound(keys in prop::collection::vec(any::<u16>(), 0..300)) {
+        let mut tree = ScapegoatTree::new();
+        for key in &keys {
+            tree.insert(*key);
+            prop_assert!(height(&tree.root) <= max_height(tree.max_length));
+        }
+        let removals: Vec<u16> = keys.iter().step_by(2).copied().collect();
+        for key in removals {
+            tree.delete(key);
+            prop_assert!(height(&tree.root) <= max_height(tree.max_length) + 1);
+        }
+    }
+}
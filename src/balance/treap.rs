@@ -0,0 +1,126 @@
+use crate::{bst, node::Node, ordered_set::OrderedSet};
+use alloc::rc::Rc;
+use core::{cell::RefCell, fmt::Debug};
+
+#[cfg(test)]
+mod treap_tests;
+
+/// A treap: a binary search tree on the keys that is also a max-heap on
+/// random priorities, which keeps it balanced in expectation. Priorities are
+/// stored in every node and drawn from a xorshift generator, so a treap built
+/// from the same seed and operations always has the same shape.
+pub struct Treap<T> {
+    root: Rc<RefCell<Node<T>>>,
+    sentinel: Rc<RefCell<Node<T>>>,
+    length: usize,
+    state: u64,
+}
+
+impl<T: PartialOrd + Clone + PartialEq + Debug + Default> Treap<T> {
+    pub fn new() -> Treap<T> {
+        Self::with_seed(0x2545_f491_4f6c_dd1d)
+    }
+
+    /// Creates an empty treap whose priorities are derived from `seed`.
+    pub fn with_seed(seed: u64) -> Treap<T> {
+        let sentinel = Rc::new(RefCell::new(Node::new_sentinel()));
+        Self {
+            root: sentinel.clone(),
+            sentinel,
+            length: 0,
+            // Xorshift gets stuck at zero.
+            state: seed.max(1),
+        }
+    }
+
+    fn next_priority(&mut self) -> u64 {
+        self.state ^= self.state << 13;
+        self.state ^= self.state >> 7;
+        self.state ^= self.state << 17;
+        self.state
+    }
+}
+
+impl<T: PartialOrd + Clone + PartialEq + Debug + Default> OrderedSet<T> for Treap<T> {
+    fn insert(&mut self, key: T) -> bool {
+        let mut z = Node::new(key);
+        z.set_left_child(self.sentinel.clone());
+        z.set_right_child(self.sentinel.clone());
+        z.rank = self.next_priority();
+        let z = Rc::new(RefCell::new(z));
+        if !bst::attach(&mut self.root, &self.sentinel, z.clone()) {
+            return false;
+        }
+
+        // Rotate the new node up until the heap property holds again.
+        loop {
+            let parent = z.borrow().parent().clone();
+            if parent.borrow().is_nil() || parent.borrow().rank >= z.borrow().rank {
+                break;
+            }
+            if Rc::ptr_eq(&z, parent.borrow().left()) {
+                bst::right_rotate(&mut self.root, parent);
+            } else {
+                bst::left_rotate(&mut self.root, parent);
+            }
+        }
+        self.length += 1;
+        true
+    }
+
+    fn delete(&mut self, key: T) {
+        let Some(z) = bst::search(&self.root, &key) else {
+            return;
+        };
+
+        // Rotate the node down below its higher priority child until it has
+        // at most one child, then splice it out.
+        loop {
+            let left = z.borrow().left().clone();
+            let right = z.borrow().right().clone();
+            if left.borrow().is_nil() || right.borrow().is_nil() {
+                break;
+            }
+            if left.borrow().rank > right.borrow().rank {
+                bst::right_rotate(&mut self.root, z.clone());
+            } else {
+                bst::left_rotate(&mut self.root, z.clone());
+            }
+        }
+        bst::remove(&mut self.root, z);
+        self.length -= 1;
+    }
+
+    fn contains_key(&self, key: T) -> bool {
+        bst::search(&self.root, &key).is_some()
+    }
+
+    fn minimum(&self) -> Option<T> {
+        bst::minimum(&self.root)
+    }
+
+    fn maximum(&self) -> Option<T> {
+        bst::maximum(&self.root)
+    }
+
+    fn len(&self) -> usize {
+        self.length
+    }
+
+    fn clear(&mut self) {
+        bst::clear(&mut self.root, &self.sentinel);
+        self.length = 0;
+    }
+}
+
+impl<T: PartialOrd + Clone + PartialEq + Debug + Default> Default for Treap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Drop for Treap<T> {
+    fn drop(&mut self) {
+        bst::clear(&mut self.root, &self.sentinel);
+    }
+}
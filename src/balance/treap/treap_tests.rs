@@ -0,0 +1,71 @@
+use crate::{balance::Treap, node::Node, ordered_set::OrderedSet};
+use proptest::prelude::*;
+use std::{cell::RefCell, rc::Rc, vec::Vec};
+
+/// Checks the max-heap property on priorities below `node` and returns the
+/// height of the subtree.
+fn check_heap<T>(node: &Rc<RefCell<Node<T>>>) -> usize {
+    if node.borrow().is_nil() {
+        return 0;
+    }
+    let node = node.borrow();
+    for child in [node.left(), node.right()] {
+        if !child.borrow().is_nil() {
+            assert!(child.borrow().rank <= node.rank, "Heap property violated");
+        }
+    }
+    1 + check_heap(node.left()).max(check_heap(node.right()))
+}
+
+#[test]
+fn test_sequential_inserts_are_shallow() {
+    let mut treap = Treap::new();
+    for key in 0..1000 {
+        treap.insert(key);
+    }
+
+    // The expected height is about 3 ln n, far below the 1000 of an
+    // unbalanced tree.
+    assert!(check_heap(&treap.root) < 50);
+}
+
+#[test]
+fn test_same_seed_gives_same_shape() {
+    let mut a = Treap::with_seed(7);
+    let mut b = Treap::with_seed(7);
+    for key in [5, 3, 8, 1, 4] {
+        a.insert(key);
+        b.insert(key);
+    }
+
+    assert_eq!(a.root.borrow().key, b.root.borrow().key);
+    assert_eq!(check_heap(&a.root), check_heap(&b.root));
+}
+
+#[test]
+fn test_delete_unlinks_sentinel_parent() {
+    let mut treap = Treap::new();
+    for key in 0..20 {
+        treap.insert(key);
+    }
+    for key in (0..20).rev() {
+        treap.delete(key);
+        assert!(treap.sentinel.borrow().parent.is_none());
+    }
+}
+
+proptest! {
+    #[test]
+    fn test_random_operations_keep_heap(keys in prop::collection::vec(any::<u8>(), 0..200)) {
+        let mut treap = Treap::new();
+        for key in &keys {
+            treap.insert(*key);
+            check_heap(&treap.root);
+        }
+        let removals: Vec<u8> = keys.iter().step_by(2).copied().collect();
+        for key in removals {
+            treap.delete(key);
+            check_heap(&treap.root);
+        }
+    }
+}
@@ -0,0 +1,260 @@
+//! Binary search tree primitives shared by every balancing strategy in the
+//! crate. They operate on the sentinel-terminated, parent-linked nodes of
+//! [`Node`] and only ever relink nodes, never move keys between them.
+
+use crate::node::Node;
+use alloc::{rc::Rc, vec};
+use core::{cell::RefCell, fmt::Debug, mem};
+
+pub(crate) fn left_rotate<T: Debug>(root: &mut Rc<RefCell<Node<T>>>, x: Rc<RefCell<Node<T>>>) {
+    // Assumes that x.right != T.nil
+    if !x.borrow().right().borrow().is_nil() {
+        // let y = x.borrow_mut().right.take().unwrap();
+        let y = x.borrow().right().clone();
+        x.borrow_mut().set_right_child(y.borrow().left().clone());
+        if !y.borrow().left().borrow().is_nil() {
+            y.borrow_mut().left_mut().borrow_mut().set_parent(x.clone());
+        }
+        y.borrow_mut().set_parent(x.borrow().parent().clone());
+        if x.borrow().parent().borrow().is_nil() {
+            *root = y.clone();
+        } else if Rc::ptr_eq(&x, x.borrow().parent().borrow().left()) {
+            x.borrow_mut()
+                .parent_mut()
+                .borrow_mut()
+                .set_left_child(y.clone());
+        } else {
+            x.borrow_mut()
+                .parent_mut()
+                .borrow_mut()
+                .set_right_child(y.clone());
+        }
+
+        y.borrow_mut().set_left_child(x.clone());
+        x.borrow_mut().set_parent(y);
+    } else {
+        panic!(
+            "Invariant violated. The right child of {:?} must not be T.nil.",
+            x
+        );
+    }
+}
+
+pub(crate) fn right_rotate<T: Debug>(root: &mut Rc<RefCell<Node<T>>>, y: Rc<RefCell<Node<T>>>) {
+    // Assumes that x.left != T.nil
+    if !y.borrow().left().borrow().is_nil() {
+        // let x = y.clone().borrow_mut().left.take().unwrap();
+        let x = y.borrow().left().clone();
+        y.borrow_mut().set_left_child(x.borrow().right().clone());
+        if !x.borrow().right().borrow().is_nil() {
+            x.borrow_mut()
+                .right_mut()
+                .borrow_mut()
+                .set_parent(y.clone());
+        }
+        x.borrow_mut().set_parent(y.borrow().parent().clone());
+        if y.borrow().parent().borrow().is_nil() {
+            *root = x.clone();
+        } else if Rc::ptr_eq(&y, y.borrow().parent().borrow().right()) {
+            y.borrow_mut()
+                .parent_mut()
+                .borrow_mut()
+                .set_right_child(x.clone());
+        } else {
+            y.borrow_mut()
+                .parent_mut()
+                .borrow_mut()
+                .set_left_child(x.clone());
+        }
+        x.borrow_mut().set_right_child(y.clone());
+        y.borrow_mut().set_parent(x);
+    } else {
+        panic!(
+            "Invariant violated. The left child of {:?} must not be T.nil.",
+            y
+        );
+    }
+}
+
+/// Replaces the subtree rooted at `u` with the subtree rooted at `v`.
+pub(crate) fn transplant<T>(
+    root: &mut Rc<RefCell<Node<T>>>,
+    u: Rc<RefCell<Node<T>>>,
+    v: Rc<RefCell<Node<T>>>,
+) {
+    if u.borrow().parent().borrow().is_nil() {
+        *root = v.clone();
+    } else if Rc::ptr_eq(&u, u.borrow().parent().borrow().left()) {
+        u.borrow_mut()
+            .parent_mut()
+            .borrow_mut()
+            .set_left_child(v.clone());
+    } else {
+        u.borrow_mut()
+            .parent_mut()
+            .borrow_mut()
+            .set_right_child(v.clone());
+    }
+    v.borrow_mut().set_parent(u.borrow().parent().clone());
+}
+
+/// Returns the node holding the smallest key below `node`.
+pub(crate) fn minimum_node<T>(node: Rc<RefCell<Node<T>>>) -> Rc<RefCell<Node<T>>> {
+    let mut x = node;
+    while !x.borrow().left().borrow().is_nil() {
+        let x_tmp = x.borrow().left().clone();
+        x = x_tmp;
+    }
+    x
+}
+
+/// Returns the node holding the largest key below `node`.
+pub(crate) fn maximum_node<T>(node: Rc<RefCell<Node<T>>>) -> Rc<RefCell<Node<T>>> {
+    let mut x = node;
+    while !x.borrow().right().borrow().is_nil() {
+        let x_tmp = x.borrow().right().clone();
+        x = x_tmp;
+    }
+    x
+}
+
+/// Returns the smallest key below `root`, or `None` if `root` is T.nil.
+pub(crate) fn minimum<T: Clone>(root: &Rc<RefCell<Node<T>>>) -> Option<T> {
+    if root.borrow().is_nil() {
+        return None;
+    }
+    let key = minimum_node(root.clone()).borrow().key.clone();
+    Some(key)
+}
+
+/// Returns the largest key below `root`, or `None` if `root` is T.nil.
+pub(crate) fn maximum<T: Clone>(root: &Rc<RefCell<Node<T>>>) -> Option<T> {
+    if root.borrow().is_nil() {
+        return None;
+    }
+    let key = maximum_node(root.clone()).borrow().key.clone();
+    Some(key)
+}
+
+/// Returns a node below `root` holding `key`.
+pub(crate) fn search<T: PartialOrd>(
+    root: &Rc<RefCell<Node<T>>>,
+    key: &T,
+) -> Option<Rc<RefCell<Node<T>>>> {
+    let mut node = root.clone();
+    while !node.borrow().is_nil() {
+        let node_tmp = if *key == node.borrow().key {
+            return Some(node);
+        } else if *key < node.borrow().key {
+            node.borrow().left().clone()
+        } else {
+            node.borrow().right().clone()
+        };
+        node = node_tmp;
+    }
+    None
+}
+
+/// Links `z` below the leaf position where its key belongs. Returns `false`
+/// without linking `z` if an equal key is already present, as in
+/// [`crate::tree::Tree::insert`]. The children of `z` must already be T.nil.
+pub(crate) fn attach<T: PartialOrd>(
+    root: &mut Rc<RefCell<Node<T>>>,
+    sentinel: &Rc<RefCell<Node<T>>>,
+    z: Rc<RefCell<Node<T>>>,
+) -> bool {
+    let mut x = root.clone();
+    let mut y = sentinel.clone();
+    while !x.borrow().is_nil() {
+        if z.borrow().key == x.borrow().key {
+            return false;
+        }
+        y = x.clone();
+        let x_tmp = if z.borrow().key < x.borrow().key {
+            x.borrow().left().clone()
+        } else {
+            x.borrow().right().clone()
+        };
+        x = x_tmp;
+    }
+
+    z.borrow_mut().set_parent(y.clone());
+    if y.borrow().is_nil() {
+        *root = z;
+    } else if z.borrow().key < y.borrow().key {
+        y.borrow_mut().set_left_child(z);
+    } else {
+        y.borrow_mut().set_right_child(z);
+    }
+    true
+}
+
+/// Unlinks `z` with the textbook successor-splicing deletion, without any
+/// rebalancing. Returns the lowest node whose subtree lost a node, which is
+/// where a bottom-up rebalancing pass has to start, or T.nil if `z` was the
+/// only node.
+pub(crate) fn remove<T>(
+    root: &mut Rc<RefCell<Node<T>>>,
+    z: Rc<RefCell<Node<T>>>,
+) -> Rc<RefCell<Node<T>>> {
+    let parent = z.borrow().parent().clone();
+    let (nil, start) = if z.borrow().left().borrow().is_nil() {
+        let nil = z.borrow().left().clone();
+        let v = z.borrow().right().clone();
+        transplant(root, z, v);
+        (nil, parent)
+    } else if z.borrow().right().borrow().is_nil() {
+        let nil = z.borrow().right().clone();
+        let v = z.borrow().left().clone();
+        transplant(root, z, v);
+        (nil, parent)
+    } else {
+        let y = minimum_node(z.borrow().right().clone());
+        let nil = y.borrow().left().clone();
+        let start = if !Rc::ptr_eq(&y, z.borrow().right()) {
+            let start = y.borrow().parent().clone();
+            let v = y.borrow().right().clone();
+            transplant(root, y.clone(), v);
+            y.borrow_mut().set_right_child(z.borrow().right().clone());
+            y.borrow_mut()
+                .right_mut()
+                .borrow_mut()
+                .set_parent(y.clone());
+            start
+        } else {
+            y.clone()
+        };
+        transplant(root, z.clone(), y.clone());
+        y.borrow_mut().set_left_child(z.borrow().left().clone());
+        y.borrow_mut().left_mut().borrow_mut().set_parent(y.clone());
+        (nil, start)
+    };
+    // Transplanting T.nil points its parent at a node of the tree. Drop that
+    // link again, so that T.nil never keeps a node alive.
+    nil.borrow_mut().parent = None;
+    start
+}
+
+/// Drops the links between all nodes below `root`. Children hold strong
+/// references to their parents, so the nodes of a subtree form reference
+/// cycles that would otherwise never be freed.
+pub(crate) fn unlink<T>(root: Rc<RefCell<Node<T>>>) {
+    let mut stack = vec![root];
+    while let Some(node) = stack.pop() {
+        let mut node = node.borrow_mut();
+        if node.is_sentinel {
+            continue;
+        }
+        node.parent = None;
+        stack.extend(node.left.take());
+        stack.extend(node.right.take());
+    }
+}
+
+/// Empties the tree rooted at `root` by pointing it at `sentinel` and
+/// dropping the links between the old nodes, including the parent of T.nil.
+pub(crate) fn clear<T>(root: &mut Rc<RefCell<Node<T>>>, sentinel: &Rc<RefCell<Node<T>>>) {
+    let old = mem::replace(root, sentinel.clone());
+    unlink(old);
+    sentinel.borrow_mut().parent = None;
+}
@@ -0,0 +1,73 @@
+use crate::multiset::MultiSet;
+use core::fmt::Debug;
+
+#[cfg(test)]
+mod heap_tests;
+
+/// A double-ended priority queue backed by a red-black [`MultiSet`].
+///
+/// Both the smallest and the largest element can be inspected in `O(log n)`
+/// and removed in `O(log n)`. Equal elements are counted in a single node.
+pub struct MinMaxHeap<T> {
+    items: MultiSet<T>,
+}
+
+impl<T: PartialOrd + Clone + PartialEq + Debug + Default> MinMaxHeap<T> {
+    pub fn new() -> MinMaxHeap<T> {
+        Self {
+            items: MultiSet::new(),
+        }
+    }
+
+    pub fn push(&mut self, item: T) {
+        self.items.insert(item);
+    }
+
+    /// Removes and returns the smallest element.
+    pub fn pop_min(&mut self) -> Option<T> {
+        self.items.pop_first()
+    }
+
+    /// Removes and returns the largest element.
+    pub fn pop_max(&mut self) -> Option<T> {
+        self.items.pop_last()
+    }
+
+    /// Returns the smallest element without removing it.
+    pub fn peek_min(&self) -> Option<&T> {
+        self.items.first()
+    }
+
+    /// Returns the largest element without removing it.
+    pub fn peek_max(&self) -> Option<&T> {
+        self.items.last()
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.items.clear();
+    }
+}
+
+impl<T: PartialOrd + Clone + PartialEq + Debug + Default> Default for MinMaxHeap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: PartialOrd + Clone + PartialEq + Debug + Default> FromIterator<T> for MinMaxHeap<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut heap = MinMaxHeap::new();
+        for item in iter {
+            heap.push(item);
+        }
+        heap
+    }
+}
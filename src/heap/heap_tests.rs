@@ -0,0 +1,82 @@
+use crate::heap::MinMaxHeap;
+use proptest::prelude::*;
+use std::{format, vec};
+
+#[test]
+fn test_empty_heap() {
+    let mut heap: MinMaxHeap<i32> = MinMaxHeap::new();
+
+    assert!(heap.is_empty());
+    assert_eq!(heap.peek_min(), None);
+    assert_eq!(heap.peek_max(), None);
+    assert_eq!(heap.pop_min(), None);
+    assert_eq!(heap.pop_max(), None);
+}
+
+#[test]
+fn test_push_and_pop_both_ends() {
+    let mut heap: MinMaxHeap<i32> = [5, 1, 9, 3, 7].into_iter().collect();
+
+    assert_eq!(heap.len(), 5);
+    assert_eq!(heap.peek_min(), Some(&1));
+    assert_eq!(heap.peek_max(), Some(&9));
+
+    assert_eq!(heap.pop_min(), Some(1));
+    assert_eq!(heap.pop_max(), Some(9));
+    assert_eq!(heap.pop_max(), Some(7));
+    assert_eq!(heap.pop_min(), Some(3));
+    assert_eq!(heap.pop_min(), Some(5));
+    assert_eq!(heap.pop_min(), None);
+    assert!(heap.is_empty());
+}
+
+#[test]
+fn test_duplicates_are_kept() {
+    let mut heap: MinMaxHeap<i32> = [2, 2, 1, 2].into_iter().collect();
+
+    assert_eq!(heap.len(), 4);
+    assert_eq!(heap.pop_max(), Some(2));
+    assert_eq!(heap.pop_max(), Some(2));
+    assert_eq!(heap.pop_max(), Some(2));
+    assert_eq!(heap.pop_max(), Some(1));
+}
+
+#[test]
+fn test_clear() {
+    let mut heap: MinMaxHeap<i32> = (0..10).collect();
+
+    heap.clear();
+
+    assert!(heap.is_empty());
+    assert_eq!(heap.peek_min(), None);
+}
+
+proptest! {
+    #[test]
+    fn test_pop_min_is_sorted(items in prop::collection::vec(any::<i16>(), 0..200)) {
+        let mut heap: MinMaxHeap<i16> = items.iter().copied().collect();
+        let mut expected = items.clone();
+        expected.sort();
+
+        let mut actual = vec![];
+        while let Some(item) = heap.pop_min() {
+            actual.push(item);
+        }
+
+        prop_assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_pop_max_is_reverse_sorted(items in prop::collection::vec(any::<i16>(), 0..200)) {
+        let mut heap: MinMaxHeap<i16> = items.iter().copied().collect();
+        let mut expected = items.clone();
+        expected.sort_by(|a, b| b.cmp(a));
+
+        let mut actual = vec![];
+        while let Some(item) = heap.pop_max() {
+            actual.push(item);
+        }
+
+        prop_assert_eq!(actual, expected);
+    }
+}
@@ -1,2 +1,20 @@
+#![no_std]
+#![cfg_attr(feature = "nightly", feature(allocator_api))]
+
+extern crate alloc;
+#[cfg(any(feature = "std", test))]
+extern crate std;
+
+pub mod aggregate;
+pub mod balance;
+pub(crate) mod bst;
+pub mod heap;
+pub mod map;
+pub mod merkle;
+pub mod multiset;
 pub(crate) mod node;
+pub mod ordered_set;
+pub mod quantile;
+pub mod sequence;
+pub mod top_down;
 pub mod tree;
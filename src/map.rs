@@ -0,0 +1,359 @@
+use crate::tree::{self, compare, Position, Tree};
+use alloc::rc::Rc;
+use core::{
+    cell::RefCell,
+    cmp::Ordering,
+    fmt::{self, Debug, Formatter},
+    mem,
+    ops::RangeBounds,
+};
+
+#[cfg(test)]
+mod map_tests;
+
+type NodeRef<K, V> = Rc<RefCell<crate::node::Node<Pair<K, V>>>>;
+
+/// A key and its value, ordered and compared by the key alone.
+#[derive(Clone, Debug, Default)]
+struct Pair<K, V> {
+    key: K,
+    value: V,
+}
+
+impl<K: PartialEq, V> PartialEq for Pair<K, V> {
+    fn eq(&self, other: &Self) -> bool {
+        self.key == other.key
+    }
+}
+
+impl<K: PartialOrd, V> PartialOrd for Pair<K, V> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.key.partial_cmp(&other.key)
+    }
+}
+
+/// A map from unique keys to values, backed by a red-black [`Tree`] of
+/// key-value pairs ordered by key.
+pub struct TreeMap<K, V> {
+    tree: Tree<Pair<K, V>>,
+}
+
+/// A view into a single key of a [`TreeMap`], which is either present or
+/// absent. Created by [`TreeMap::entry`].
+pub enum Entry<'a, K, V> {
+    Occupied(OccupiedEntry<'a, K, V>),
+    Vacant(VacantEntry<'a, K, V>),
+}
+
+/// A key that is present in a [`TreeMap`].
+pub struct OccupiedEntry<'a, K, V> {
+    tree: &'a mut Tree<Pair<K, V>>,
+    node: NodeRef<K, V>,
+}
+
+/// A key that is absent from a [`TreeMap`], together with the position the
+/// search for it ended at, so that inserting it does not search again.
+pub struct VacantEntry<'a, K, V> {
+    tree: &'a mut Tree<Pair<K, V>>,
+    key: K,
+    parent: NodeRef<K, V>,
+    as_left: bool,
+}
+
+/// An iterator over the entries of a [`TreeMap`] in ascending key order.
+/// Created by [`TreeMap::iter`].
+pub struct Iter<'a, K, V> {
+    inner: tree::Iter<'a, Pair<K, V>>,
+}
+
+impl<K, V> TreeMap<K, V>
+where
+    K: PartialOrd + Clone + Debug + Default,
+    V: Clone + Debug + Default,
+{
+    pub fn new() -> TreeMap<K, V> {
+        Self { tree: Tree::new() }
+    }
+
+    /// Inserts `value` under `key` and returns the value it replaces, if any.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        match self.entry(key) {
+            Entry::Occupied(mut entry) => Some(entry.insert(value)),
+            Entry::Vacant(entry) => {
+                entry.insert(value);
+                None
+            }
+        }
+    }
+
+    pub fn get(&self, key: &K) -> Option<&V> {
+        match self.position(key) {
+            Position::Found(node) => Some(&self.tree.key_ref(&node).value),
+            Position::Vacant { .. } => None,
+        }
+    }
+
+    pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        match self.position(key) {
+            Position::Found(node) => Some(&mut self.tree.key_mut(&node).value),
+            Position::Vacant { .. } => None,
+        }
+    }
+
+    pub fn contains_key(&self, key: &K) -> bool {
+        matches!(self.position(key), Position::Found(_))
+    }
+
+    /// Removes `key` and returns its value, if it was present.
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        match self.position(key) {
+            Position::Found(node) => Some(self.tree.remove_node(node).value),
+            Position::Vacant { .. } => None,
+        }
+    }
+
+    /// Returns the entry for `key`, searching the tree once for both reading
+    /// and inserting.
+    ///
+    /// ```
+    /// use atlas_rb_tree::map::TreeMap;
+    ///
+    /// let mut counts = TreeMap::new();
+    /// for word in ["a", "b", "a"] {
+    ///     *counts.entry(word).or_insert(0) += 1;
+    /// }
+    ///
+    /// assert_eq!(counts.get(&"a"), Some(&2));
+    /// assert_eq!(counts.get(&"b"), Some(&1));
+    /// ```
+    pub fn entry(&mut self, key: K) -> Entry<'_, K, V> {
+        match self.position(&key) {
+            Position::Found(node) => Entry::Occupied(OccupiedEntry {
+                tree: &mut self.tree,
+                node,
+            }),
+            Position::Vacant { parent, as_left } => Entry::Vacant(VacantEntry {
+                tree: &mut self.tree,
+                key,
+                parent,
+                as_left,
+            }),
+        }
+    }
+
+    /// Returns the number of keys within `range` in O(log n).
+    pub fn count_range<R: RangeBounds<K>>(&self, range: R) -> usize {
+        let pair = |key: &K| Pair {
+            key: key.clone(),
+            value: V::default(),
+        };
+        self.tree
+            .count_range((range.start_bound().map(pair), range.end_bound().map(pair)))
+    }
+
+    /// Returns the entry with the smallest key.
+    pub fn first_key_value(&self) -> Option<(&K, &V)> {
+        self.tree.first().map(|pair| (&pair.key, &pair.value))
+    }
+
+    /// Returns the entry with the largest key.
+    pub fn last_key_value(&self) -> Option<(&K, &V)> {
+        self.tree.last().map(|pair| (&pair.key, &pair.value))
+    }
+
+    /// Returns the entry with the smallest key for in-place manipulation.
+    pub fn first_entry(&mut self) -> Option<OccupiedEntry<'_, K, V>> {
+        let node = self.tree.first_node()?;
+        Some(OccupiedEntry {
+            tree: &mut self.tree,
+            node,
+        })
+    }
+
+    /// Returns the entry with the largest key for in-place manipulation.
+    pub fn last_entry(&mut self) -> Option<OccupiedEntry<'_, K, V>> {
+        let node = self.tree.last_node()?;
+        Some(OccupiedEntry {
+            tree: &mut self.tree,
+            node,
+        })
+    }
+
+    /// Returns an iterator over the entries in ascending key order.
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        Iter {
+            inner: self.tree.iter(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.tree.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tree.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.tree.clear();
+    }
+
+    fn position(&self, key: &K) -> Position<Pair<K, V>> {
+        self.tree.position_by(|pair| compare(key, &pair.key))
+    }
+}
+
+impl<'a, K, V> Entry<'a, K, V>
+where
+    K: PartialOrd + Clone + Debug + Default,
+    V: Clone + Debug + Default,
+{
+    /// Returns the value of the entry, inserting `default` first if the key
+    /// is absent.
+    pub fn or_insert(self, default: V) -> &'a mut V {
+        match self {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(default),
+        }
+    }
+
+    /// Returns the value of the entry, inserting the result of `default`
+    /// first if the key is absent.
+    pub fn or_insert_with<F: FnOnce() -> V>(self, default: F) -> &'a mut V {
+        match self {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(default()),
+        }
+    }
+
+    /// Returns the value of the entry, inserting `V::default()` first if the
+    /// key is absent.
+    pub fn or_default(self) -> &'a mut V {
+        self.or_insert_with(V::default)
+    }
+
+    /// Calls `f` on the value if the key is present.
+    pub fn and_modify<F: FnOnce(&mut V)>(mut self, f: F) -> Self {
+        if let Entry::Occupied(entry) = &mut self {
+            f(entry.get_mut());
+        }
+        self
+    }
+
+    pub fn key(&self) -> &K {
+        match self {
+            Entry::Occupied(entry) => entry.key(),
+            Entry::Vacant(entry) => entry.key(),
+        }
+    }
+}
+
+impl<'a, K, V> OccupiedEntry<'a, K, V>
+where
+    K: PartialOrd + Clone + Debug + Default,
+    V: Clone + Debug + Default,
+{
+    pub fn key(&self) -> &K {
+        &self.tree.key_ref(&self.node).key
+    }
+
+    pub fn get(&self) -> &V {
+        &self.tree.key_ref(&self.node).value
+    }
+
+    pub fn get_mut(&mut self) -> &mut V {
+        &mut self.tree.key_mut(&self.node).value
+    }
+
+    /// Returns the value borrowed for as long as the map is.
+    pub fn into_mut(self) -> &'a mut V {
+        &mut self.tree.key_mut(&self.node).value
+    }
+
+    /// Replaces the value and returns the old one.
+    pub fn insert(&mut self, value: V) -> V {
+        mem::replace(self.get_mut(), value)
+    }
+
+    /// Removes the entry from the map and returns its value.
+    pub fn remove(self) -> V {
+        self.remove_entry().1
+    }
+
+    /// Removes the entry from the map and returns its key and value.
+    pub fn remove_entry(self) -> (K, V) {
+        let pair = self.tree.remove_node(self.node);
+        (pair.key, pair.value)
+    }
+}
+
+impl<'a, K, V> VacantEntry<'a, K, V>
+where
+    K: PartialOrd + Clone + Debug + Default,
+    V: Clone + Debug + Default,
+{
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+
+    pub fn into_key(self) -> K {
+        self.key
+    }
+
+    /// Inserts the key with `value` at the position found by the search and
+    /// returns the value borrowed for as long as the map is.
+    pub fn insert(self, value: V) -> &'a mut V {
+        let pair = Pair {
+            key: self.key,
+            value,
+        };
+        let node = self.tree.insert_vacant(self.parent, self.as_left, pair);
+        &mut self.tree.key_mut(&node).value
+    }
+}
+
+impl<'a, K, V> Iterator for Iter<'a, K, V>
+where
+    K: PartialOrd + Clone + Debug + Default,
+    V: Clone + Debug + Default,
+{
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|pair| (&pair.key, &pair.value))
+    }
+}
+
+impl<K, V> Default for TreeMap<K, V>
+where
+    K: PartialOrd + Clone + Debug + Default,
+    V: Clone + Debug + Default,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V> FromIterator<(K, V)> for TreeMap<K, V>
+where
+    K: PartialOrd + Clone + Debug + Default,
+    V: Clone + Debug + Default,
+{
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut map = TreeMap::new();
+        for (key, value) in iter {
+            map.insert(key, value);
+        }
+        map
+    }
+}
+
+impl<K, V> Debug for TreeMap<K, V>
+where
+    K: PartialOrd + Clone + Debug + Default,
+    V: Clone + Debug + Default,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
@@ -0,0 +1,161 @@
+use crate::map::{Entry, TreeMap};
+use proptest::prelude::*;
+use std::{collections::BTreeMap, format, string::String, vec::Vec};
+
+#[test]
+fn test_insert_and_get() {
+    let mut map = TreeMap::new();
+
+    assert_eq!(map.insert(2, "b"), None);
+    assert_eq!(map.insert(1, "a"), None);
+    assert_eq!(map.insert(2, "B"), Some("b"));
+
+    assert_eq!(map.len(), 2);
+    assert_eq!(map.get(&1), Some(&"a"));
+    assert_eq!(map.get(&2), Some(&"B"));
+    assert_eq!(map.get(&3), None);
+}
+
+#[test]
+fn test_remove() {
+    let mut map: TreeMap<i32, i32> = (0..10).map(|key| (key, key * key)).collect();
+
+    assert_eq!(map.remove(&3), Some(9));
+    assert_eq!(map.remove(&3), None);
+
+    assert_eq!(map.len(), 9);
+    assert!(!map.contains_key(&3));
+}
+
+#[test]
+fn test_get_mut() {
+    let mut map: TreeMap<i32, i32> = [(1, 10)].into_iter().collect();
+
+    *map.get_mut(&1).unwrap() += 5;
+
+    assert_eq!(map.get(&1), Some(&15));
+    assert_eq!(map.get_mut(&2), None);
+}
+
+#[test]
+fn test_entry_counts_words() {
+    let mut counts = TreeMap::new();
+    for word in "the cat saw the other cat and the dog".split(' ') {
+        *counts.entry(String::from(word)).or_default() += 1;
+    }
+
+    let counts: Vec<(&str, u32)> = counts.iter().map(|(k, v)| (k.as_str(), *v)).collect();
+    assert_eq!(
+        counts,
+        [
+            ("and", 1),
+            ("cat", 2),
+            ("dog", 1),
+            ("other", 1),
+            ("saw", 1),
+            ("the", 3)
+        ]
+    );
+}
+
+#[test]
+fn test_entry_or_insert_with_only_calls_default_when_vacant() {
+    let mut map = TreeMap::new();
+    let mut calls = 0;
+
+    for _ in 0..3 {
+        map.entry(1).or_insert_with(|| {
+            calls += 1;
+            7
+        });
+    }
+
+    assert_eq!(calls, 1);
+    assert_eq!(map.get(&1), Some(&7));
+}
+
+#[test]
+fn test_entry_and_modify() {
+    let mut map = TreeMap::new();
+
+    map.entry(1).and_modify(|v| *v += 1).or_insert(10);
+    map.entry(1).and_modify(|v| *v += 1).or_insert(10);
+
+    assert_eq!(map.get(&1), Some(&11));
+}
+
+#[test]
+fn test_occupied_entry() {
+    let mut map: TreeMap<i32, i32> = [(1, 10), (2, 20)].into_iter().collect();
+
+    let Entry::Occupied(mut entry) = map.entry(1) else {
+        panic!("Expected an occupied entry");
+    };
+    assert_eq!(entry.key(), &1);
+    assert_eq!(entry.get(), &10);
+    assert_eq!(entry.insert(11), 10);
+    assert_eq!(entry.remove(), 11);
+
+    assert_eq!(map.len(), 1);
+    assert!(!map.contains_key(&1));
+}
+
+#[test]
+fn test_vacant_entry() {
+    let mut map: TreeMap<i32, i32> = TreeMap::new();
+
+    let Entry::Vacant(entry) = map.entry(5) else {
+        panic!("Expected a vacant entry");
+    };
+    assert_eq!(entry.key(), &5);
+    *entry.insert(1) += 1;
+
+    assert_eq!(map.get(&5), Some(&2));
+}
+
+#[test]
+fn test_count_range() {
+    let map: TreeMap<i32, i32> = (0..20).map(|key| (key * 2, key)).collect();
+
+    assert_eq!(map.count_range(4..10), 3);
+    assert_eq!(map.count_range(4..=10), 4);
+    assert_eq!(map.count_range(..), 20);
+}
+
+#[test]
+fn test_debug() {
+    let map: TreeMap<i32, char> = [(2, 'b'), (1, 'a')].into_iter().collect();
+
+    assert_eq!(format!("{:?}", map), "{1: 'a', 2: 'b'}");
+}
+
+#[test]
+fn test_incomparable_key_matches_no_other_key() {
+    let mut map = TreeMap::new();
+    map.insert(1.0, "one");
+
+    assert_eq!(map.insert(f64::NAN, "nan"), None);
+
+    assert_eq!(map.len(), 2);
+    assert_eq!(map.get(&1.0), Some(&"one"));
+    assert_eq!(map.get(&f64::NAN), None);
+}
+
+proptest! {
+    #[test]
+    fn test_matches_btree_map(ops in prop::collection::vec((any::<u8>(), any::<bool>()), 0..200)) {
+        let mut map = TreeMap::new();
+        let mut model = BTreeMap::new();
+        for (key, insert) in ops {
+            if insert {
+                *map.entry(key).or_insert(0u32) += 1;
+                *model.entry(key).or_insert(0u32) += 1;
+            } else {
+                prop_assert_eq!(map.remove(&key), model.remove(&key));
+            }
+            prop_assert_eq!(map.len(), model.len());
+        }
+        prop_assert!(map.iter().eq(model.iter()));
+        prop_assert_eq!(map.tree.validate(), Ok(()));
+    }
+}
@@ -0,0 +1,405 @@
+use crate::{
+    node::Node,
+    ordered_set::OrderedSet,
+    tree::{self, compare, Augmentation, Position, Tree},
+};
+use alloc::{rc::Rc, vec::Vec};
+use core::{
+    cell::RefCell,
+    cmp::Ordering,
+    fmt::{self, Debug, Formatter},
+    hash::{Hash, Hasher},
+};
+
+#[cfg(test)]
+mod merkle_tests;
+
+type NodeRef<T> = Rc<RefCell<Node<Hashed<T>>>>;
+
+/// The multiplier of the polynomial hash of a run of keys. Any odd number
+/// keeps every power of it distinct from 0.
+const BASE: u64 = 0x9e37_79b9_7f4a_7c15;
+
+/// FNV-1a, which unlike the hashers of `std` hashes the same bytes to the
+/// same value in every process.
+struct StableHasher(u64);
+
+impl Hasher for StableHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 = (self.0 ^ byte as u64).wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+
+    fn finish(&self) -> u64 {
+        // The finalizer of SplitMix64, so that keys that differ in few bits
+        // still differ in about half of the bits of their hashes.
+        let mut hash = self.0;
+        hash = (hash ^ (hash >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        hash = (hash ^ (hash >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        hash ^ (hash >> 31)
+    }
+}
+
+/// The hash of a run of keys in ascending order: the sum of
+/// `hash(key) * BASE^i` over the keys, `i` counting from the last one,
+/// together with `BASE^len`.
+///
+/// Joining two runs only needs their hashes, so the hash of a subtree is
+/// computed from the hashes of its children like in a Merkle tree. Unlike in
+/// a Merkle tree it does not depend on the shape of the subtree, so two
+/// trees with the same keys agree on it however they were balanced.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Run {
+    hash: u64,
+    power: u64,
+}
+
+impl Run {
+    const EMPTY: Run = Run { hash: 0, power: 1 };
+
+    fn of<T: Hash>(key: &T) -> Run {
+        let mut hasher = StableHasher(0xcbf2_9ce4_8422_2325);
+        key.hash(&mut hasher);
+        Run {
+            hash: hasher.finish(),
+            power: BASE,
+        }
+    }
+
+    /// Returns the run of the keys of `self` followed by those of `later`.
+    fn then(&self, later: &Run) -> Run {
+        Run {
+            hash: self.hash.wrapping_mul(later.power).wrapping_add(later.hash),
+            power: self.power.wrapping_mul(later.power),
+        }
+    }
+}
+
+/// A key, ordered and compared by itself alone, together with the run of
+/// the keys in the subtree of its node.
+#[derive(Debug, Clone)]
+struct Hashed<T> {
+    key: T,
+    run: Run,
+}
+
+impl<T: Default> Default for Hashed<T> {
+    fn default() -> Self {
+        Self {
+            key: T::default(),
+            run: Run::EMPTY,
+        }
+    }
+}
+
+impl<T: PartialEq> PartialEq for Hashed<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.key == other.key
+    }
+}
+
+impl<T: PartialOrd> PartialOrd for Hashed<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.key.partial_cmp(&other.key)
+    }
+}
+
+/// A key that is in only one of two [`MerkleSet`]s. Returned by
+/// [`MerkleSet::diff`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Difference<'a, T> {
+    OnlyInSelf(&'a T),
+    OnlyInOther(&'a T),
+}
+
+/// A set that keeps a hash of the keys in every subtree of its red-black
+/// tree, for replicas that need to find out quickly whether and where they
+/// differ.
+///
+/// Equal sets have equal [`MerkleSet::digest`]s whatever order their keys
+/// were inserted in, and [`MerkleSet::diff`] skips every subtree whose keys
+/// hash the same as the same range of the other set. The hashes are not
+/// cryptographic: sets that differ have equal digests with a probability of
+/// about 2^-64, and anyone who chooses the keys can make them collide.
+///
+/// The keys are hashed with their [`Hash`] impl, which for the types in
+/// `core` and `alloc` writes the same bytes on every platform of the same
+/// endianness and pointer width.
+///
+/// ```
+/// use atlas_rb_tree::merkle::{Difference, MerkleSet};
+///
+/// let primary: MerkleSet<u32> = (0..1000).collect();
+/// let mut replica: MerkleSet<u32> = (0..1000).rev().collect();
+/// assert_eq!(primary.digest(), replica.digest());
+///
+/// replica.remove(&500);
+/// replica.insert(2000);
+/// assert_ne!(primary.digest(), replica.digest());
+/// assert_eq!(
+///     primary.diff(&replica),
+///     [Difference::OnlyInSelf(&500), Difference::OnlyInOther(&2000)]
+/// );
+/// ```
+pub struct MerkleSet<T> {
+    tree: Tree<Hashed<T>>,
+}
+
+/// An iterator over the keys of a [`MerkleSet`] in ascending order. Created
+/// by [`MerkleSet::iter`].
+pub struct Iter<'a, T> {
+    inner: tree::Iter<'a, Hashed<T>>,
+}
+
+/// Recomputes the run of `hashed` from its key and the runs of its children.
+fn update<T: Hash>(hashed: &mut Hashed<T>, left: Option<&Hashed<T>>, right: Option<&Hashed<T>>) {
+    let left = left.map_or(Run::EMPTY, |left| left.run);
+    let right = right.map_or(Run::EMPTY, |right| right.run);
+    hashed.run = left.then(&Run::of(&hashed.key)).then(&right);
+}
+
+impl<T: PartialOrd + Clone + Debug + Default + Hash> MerkleSet<T> {
+    pub fn new() -> MerkleSet<T> {
+        Self {
+            tree: Tree::with_augmentation(Augmentation {
+                update: update::<T>,
+                push_down: None,
+            }),
+        }
+    }
+
+    /// Adds `key` to the set. Returns `false` and leaves the set unchanged
+    /// if an equal key is already present.
+    pub fn insert(&mut self, key: T) -> bool {
+        match self.position(&key) {
+            Position::Found(_) => false,
+            Position::Vacant { parent, as_left } => {
+                let hashed = Hashed {
+                    key,
+                    run: Run::EMPTY,
+                };
+                self.tree.insert_vacant(parent, as_left, hashed);
+                true
+            }
+        }
+    }
+
+    /// Removes `key` from the set. Returns `false` if it was absent.
+    pub fn remove(&mut self, key: &T) -> bool {
+        match self.position(key) {
+            Position::Found(node) => {
+                self.tree.remove_node(node);
+                true
+            }
+            Position::Vacant { .. } => false,
+        }
+    }
+
+    pub fn contains(&self, key: &T) -> bool {
+        matches!(self.position(key), Position::Found(_))
+    }
+
+    /// Returns the hash of all keys in O(1). Sets with the same keys have
+    /// the same digest.
+    pub fn digest(&self) -> u64 {
+        run_of(self.tree.root_node()).hash
+    }
+
+    /// Returns the keys that are in only one of `self` and `other`, in
+    /// ascending order.
+    ///
+    /// The search descends the tree of `self` and compares the hash of every
+    /// subtree with the hash of the same key range in `other`, computed in
+    /// O(log n). Subtrees whose hashes agree are skipped, so for `d`
+    /// differences this takes O(d log² n) instead of visiting every key.
+    pub fn diff<'a>(&'a self, other: &'a MerkleSet<T>) -> Vec<Difference<'a, T>> {
+        let mut differences = Vec::new();
+        self.diff_within(self.tree.root_node(), other, None, None, &mut differences);
+        differences
+    }
+
+    pub fn len(&self) -> usize {
+        self.tree.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tree.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.tree.clear();
+    }
+
+    /// Returns an iterator over the keys in ascending order.
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            inner: self.tree.iter(),
+        }
+    }
+
+    fn position(&self, key: &T) -> Position<Hashed<T>> {
+        self.tree.position_by(|hashed| compare(key, &hashed.key))
+    }
+
+    /// Adds the differences between the subtree of `node`, which holds the
+    /// keys of `self` strictly between `start` and `end`, and the keys of
+    /// `other` in the same range.
+    fn diff_within<'a>(
+        &'a self,
+        node: &NodeRef<T>,
+        other: &'a MerkleSet<T>,
+        start: Option<&'a T>,
+        end: Option<&'a T>,
+        differences: &mut Vec<Difference<'a, T>>,
+    ) {
+        if run_of(node) == run_within(other.tree.root_node(), start, end) {
+            return;
+        }
+        if node.borrow().is_nil() {
+            other.keys_within(other.tree.root_node(), start, end, differences);
+            return;
+        }
+
+        let key = &self.tree.key_ref(node).key;
+        let left = node.borrow().left().clone();
+        let right = node.borrow().right().clone();
+        self.diff_within(&left, other, start, Some(key), differences);
+        if !other.contains(key) {
+            differences.push(Difference::OnlyInSelf(key));
+        }
+        self.diff_within(&right, other, Some(key), end, differences);
+    }
+
+    /// Adds the keys below `node` that are strictly between `start` and
+    /// `end` as only in the other set.
+    fn keys_within<'a>(
+        &'a self,
+        node: &NodeRef<T>,
+        start: Option<&T>,
+        end: Option<&T>,
+        differences: &mut Vec<Difference<'a, T>>,
+    ) {
+        if node.borrow().is_nil() {
+            return;
+        }
+        let key = &self.tree.key_ref(node).key;
+        let left = node.borrow().left().clone();
+        let right = node.borrow().right().clone();
+        let after_start = match start {
+            Some(start) => start < key,
+            None => true,
+        };
+        let before_end = match end {
+            Some(end) => key < end,
+            None => true,
+        };
+        if after_start {
+            self.keys_within(&left, start, end, differences);
+        }
+        if after_start && before_end {
+            differences.push(Difference::OnlyInOther(key));
+        }
+        if before_end {
+            self.keys_within(&right, start, end, differences);
+        }
+    }
+}
+
+/// Returns the run of the subtree of `node`, which is empty for T.nil.
+fn run_of<T>(node: &NodeRef<T>) -> Run {
+    let node = node.borrow();
+    if node.is_nil() {
+        Run::EMPTY
+    } else {
+        node.key.run
+    }
+}
+
+/// Returns the run of the keys below `node` that are strictly between
+/// `start` and `end`, `None` standing for no bound. Like the range folds of
+/// [`AggregateMap`](crate::aggregate::AggregateMap), this combines the runs
+/// of O(log n) subtrees.
+fn run_within<T: PartialOrd + Hash>(node: &NodeRef<T>, start: Option<&T>, end: Option<&T>) -> Run {
+    let node = node.borrow();
+    if node.is_nil() {
+        return Run::EMPTY;
+    }
+    if start.is_none() && end.is_none() {
+        return node.key.run;
+    }
+
+    let key = &node.key.key;
+    if start.is_some_and(|start| key <= start) {
+        run_within(node.right(), start, end)
+    } else if end.is_some_and(|end| end <= key) {
+        run_within(node.left(), start, end)
+    } else {
+        let left = run_within(node.left(), start, None);
+        let right = run_within(node.right(), None, end);
+        left.then(&Run::of(key)).then(&right)
+    }
+}
+
+impl<'a, T> Iterator for Iter<'a, T>
+where
+    T: PartialOrd + Clone + Debug + Default,
+{
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        self.inner.next().map(|hashed| &hashed.key)
+    }
+}
+
+impl<T: PartialOrd + Clone + Debug + Default + Hash> OrderedSet<T> for MerkleSet<T> {
+    fn insert(&mut self, key: T) -> bool {
+        MerkleSet::insert(self, key)
+    }
+
+    fn delete(&mut self, key: T) {
+        self.remove(&key);
+    }
+
+    fn contains_key(&self, key: T) -> bool {
+        self.contains(&key)
+    }
+
+    fn minimum(&self) -> Option<T> {
+        self.tree.first().map(|hashed| hashed.key.clone())
+    }
+
+    fn maximum(&self) -> Option<T> {
+        self.tree.last().map(|hashed| hashed.key.clone())
+    }
+
+    fn len(&self) -> usize {
+        MerkleSet::len(self)
+    }
+
+    fn clear(&mut self) {
+        MerkleSet::clear(self)
+    }
+}
+
+impl<T: PartialOrd + Clone + Debug + Default + Hash> Default for MerkleSet<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: PartialOrd + Clone + Debug + Default + Hash> FromIterator<T> for MerkleSet<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut set = MerkleSet::new();
+        for key in iter {
+            set.insert(key);
+        }
+        set
+    }
+}
+
+impl<T: PartialOrd + Clone + Debug + Default + Hash> Debug for MerkleSet<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
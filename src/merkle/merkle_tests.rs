@@ -0,0 +1,158 @@
+use crate::merkle::{Difference, MerkleSet};
+use core::{
+    cell::Cell,
+    cmp::Ordering,
+    hash::{Hash, Hasher},
+};
+use proptest::prelude::*;
+use std::{collections::BTreeSet, format, thread_local, vec::Vec};
+
+thread_local! {
+    static COMPARISONS: Cell<usize> = const { Cell::new(0) };
+}
+
+/// A key that counts how often it is compared.
+#[derive(Debug, Clone, Copy, Default)]
+struct Counted(i32);
+
+impl PartialEq for Counted {
+    fn eq(&self, other: &Self) -> bool {
+        COMPARISONS.with(|count| count.set(count.get() + 1));
+        self.0 == other.0
+    }
+}
+
+impl PartialOrd for Counted {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        COMPARISONS.with(|count| count.set(count.get() + 1));
+        self.0.partial_cmp(&other.0)
+    }
+}
+
+impl Hash for Counted {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.hash(state);
+    }
+}
+
+/// A float key, hashed by its bits, whose NaN compares with nothing.
+#[derive(Debug, Clone, Copy, Default, PartialEq, PartialOrd)]
+struct Float(f64);
+
+impl Hash for Float {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.to_bits().hash(state);
+    }
+}
+
+#[test]
+fn test_digest_ignores_insertion_order() {
+    let ascending: MerkleSet<i32> = (0..500).collect();
+    let descending: MerkleSet<i32> = (0..500).rev().collect();
+    let mut shuffled: MerkleSet<i32> = (0..500).map(|key| key * 7919 % 500).collect();
+
+    assert_eq!(ascending.digest(), descending.digest());
+    assert_eq!(ascending.digest(), shuffled.digest());
+
+    shuffled.remove(&250);
+    assert_ne!(ascending.digest(), shuffled.digest());
+    shuffled.insert(250);
+    assert_eq!(ascending.digest(), shuffled.digest());
+}
+
+#[test]
+fn test_digest_depends_on_keys() {
+    let empty = MerkleSet::<i32>::new();
+    let zero: MerkleSet<i32> = [0].into_iter().collect();
+    let one: MerkleSet<i32> = [1].into_iter().collect();
+    let both: MerkleSet<i32> = [0, 1].into_iter().collect();
+
+    let digests = [empty.digest(), zero.digest(), one.digest(), both.digest()];
+    for (i, digest) in digests.iter().enumerate() {
+        assert!(!digests[..i].contains(digest));
+    }
+}
+
+#[test]
+fn test_diff() {
+    let left: MerkleSet<i32> = (0..100).collect();
+    let mut right: MerkleSet<i32> = (0..100).rev().collect();
+    right.remove(&0);
+    right.remove(&42);
+    right.insert(-5);
+    right.insert(150);
+
+    assert!(left.diff(&left).is_empty());
+    assert_eq!(
+        left.diff(&right),
+        [
+            Difference::OnlyInOther(&-5),
+            Difference::OnlyInSelf(&0),
+            Difference::OnlyInSelf(&42),
+            Difference::OnlyInOther(&150),
+        ]
+    );
+    assert_eq!(
+        right.diff(&MerkleSet::new()).len(),
+        right.len(),
+        "everything differs from an empty set"
+    );
+}
+
+#[test]
+fn test_diff_skips_equal_subtrees() {
+    let left: MerkleSet<Counted> = (0..4096).map(Counted).collect();
+    let mut right: MerkleSet<Counted> = (0..4096).rev().map(Counted).collect();
+    right.remove(&Counted(1234));
+
+    let before = COMPARISONS.with(Cell::get);
+    let differences = left.diff(&right);
+    let comparisons = COMPARISONS.with(Cell::get) - before;
+
+    assert_eq!(differences, [Difference::OnlyInSelf(&Counted(1234))]);
+    assert!(comparisons < 4096, "{comparisons} comparisons");
+}
+
+#[test]
+fn test_debug() {
+    let set: MerkleSet<i32> = [2, 1].into_iter().collect();
+
+    assert_eq!(format!("{:?}", set), "{1, 2}");
+}
+
+#[test]
+fn test_incomparable_key_matches_no_other_key() {
+    let mut set = MerkleSet::new();
+    set.insert(Float(1.0));
+
+    assert!(set.insert(Float(f64::NAN)));
+
+    assert_eq!(set.len(), 2);
+    assert!(set.contains(&Float(1.0)));
+    assert!(!set.contains(&Float(f64::NAN)));
+}
+
+proptest! {
+    #[test]
+    fn test_diff_matches_btree_set(
+        left in prop::collection::vec(0u8..64, 0..100),
+        right in prop::collection::vec(0u8..64, 0..100),
+    ) {
+        let left_set: MerkleSet<u8> = left.iter().copied().collect();
+        let right_set: MerkleSet<u8> = right.iter().copied().collect();
+        let left: BTreeSet<_> = left.into_iter().collect();
+        let right: BTreeSet<_> = right.into_iter().collect();
+
+        let mut expected: Vec<_> = left
+            .difference(&right)
+            .map(Difference::OnlyInSelf)
+            .chain(right.difference(&left).map(Difference::OnlyInOther))
+            .collect();
+        expected.sort_by_key(|difference| match difference {
+            Difference::OnlyInSelf(key) | Difference::OnlyInOther(key) => **key,
+        });
+        prop_assert_eq!(left_set.diff(&right_set), expected);
+        prop_assert_eq!(left_set.digest() == right_set.digest(), left == right);
+        prop_assert_eq!(left_set.tree.validate(), Ok(()));
+    }
+}
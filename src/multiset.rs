@@ -0,0 +1,173 @@
+use crate::map::{self, Entry, TreeMap};
+use core::fmt::{self, Debug, Formatter};
+
+#[cfg(test)]
+mod multiset_tests;
+
+/// An ordered collection that counts equal elements instead of storing them
+/// apart.
+///
+/// [`Tree`](crate::tree::Tree) keeps one element per key and rejects
+/// duplicates. A `MultiSet` stores every distinct key once, in a red-black
+/// tree node together with the number of times it was inserted.
+pub struct MultiSet<T> {
+    counts: TreeMap<T, usize>,
+    /// The number of elements, counting duplicates.
+    length: usize,
+}
+
+/// An iterator over the elements of a [`MultiSet`] in ascending order, each
+/// repeated as often as it was inserted. Created by [`MultiSet::iter`].
+pub struct Iter<'a, T> {
+    counts: map::Iter<'a, T, usize>,
+    current: Option<(&'a T, usize)>,
+}
+
+impl<T: PartialOrd + Clone + Debug + Default> MultiSet<T> {
+    pub fn new() -> MultiSet<T> {
+        Self {
+            counts: TreeMap::new(),
+            length: 0,
+        }
+    }
+
+    /// Adds one occurrence of `key` and returns how many there are now.
+    pub fn insert(&mut self, key: T) -> usize {
+        self.length += 1;
+        let count = self.counts.entry(key).or_insert(0);
+        *count += 1;
+        *count
+    }
+
+    /// Returns how many times `key` occurs.
+    pub fn count(&self, key: &T) -> usize {
+        self.counts.get(key).copied().unwrap_or(0)
+    }
+
+    pub fn contains(&self, key: &T) -> bool {
+        self.counts.contains_key(key)
+    }
+
+    /// Removes one occurrence of `key`. Returns `false` if there was none.
+    pub fn remove_one(&mut self, key: &T) -> bool {
+        match self.counts.entry(key.clone()) {
+            Entry::Occupied(entry) => {
+                remove_one_of(entry);
+                self.length -= 1;
+                true
+            }
+            Entry::Vacant(_) => false,
+        }
+    }
+
+    /// Removes every occurrence of `key` and returns how many there were.
+    pub fn remove_all(&mut self, key: &T) -> usize {
+        let count = self.counts.remove(key).unwrap_or(0);
+        self.length -= count;
+        count
+    }
+
+    /// Returns the smallest element.
+    pub fn first(&self) -> Option<&T> {
+        self.counts.first_key_value().map(|(key, _)| key)
+    }
+
+    /// Returns the largest element.
+    pub fn last(&self) -> Option<&T> {
+        self.counts.last_key_value().map(|(key, _)| key)
+    }
+
+    /// Removes and returns one occurrence of the smallest element.
+    pub fn pop_first(&mut self) -> Option<T> {
+        let key = remove_one_of(self.counts.first_entry()?);
+        self.length -= 1;
+        Some(key)
+    }
+
+    /// Removes and returns one occurrence of the largest element.
+    pub fn pop_last(&mut self) -> Option<T> {
+        let key = remove_one_of(self.counts.last_entry()?);
+        self.length -= 1;
+        Some(key)
+    }
+
+    /// Returns the number of elements, counting duplicates.
+    pub fn len(&self) -> usize {
+        self.length
+    }
+
+    /// Returns the number of distinct elements.
+    pub fn distinct_len(&self) -> usize {
+        self.counts.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.length == 0
+    }
+
+    pub fn clear(&mut self) {
+        self.counts.clear();
+        self.length = 0;
+    }
+
+    /// Returns an iterator over the elements in ascending order, with every
+    /// element repeated as often as it occurs.
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            counts: self.counts.iter(),
+            current: None,
+        }
+    }
+}
+
+/// Decrements the count of `entry`, removing the node once it reaches 0, and
+/// returns a copy of the key.
+fn remove_one_of<T>(mut entry: map::OccupiedEntry<'_, T, usize>) -> T
+where
+    T: PartialOrd + Clone + Debug + Default,
+{
+    if *entry.get() > 1 {
+        *entry.get_mut() -= 1;
+        entry.key().clone()
+    } else {
+        entry.remove_entry().0
+    }
+}
+
+impl<'a, T> Iterator for Iter<'a, T>
+where
+    T: PartialOrd + Clone + Debug + Default,
+{
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        if matches!(self.current, None | Some((_, 0))) {
+            self.current = self.counts.next().map(|(key, count)| (key, *count));
+        }
+        let (key, remaining) = self.current.as_mut()?;
+        *remaining -= 1;
+        Some(*key)
+    }
+}
+
+impl<T: PartialOrd + Clone + Debug + Default> Default for MultiSet<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: PartialOrd + Clone + Debug + Default> FromIterator<T> for MultiSet<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut set = MultiSet::new();
+        for key in iter {
+            set.insert(key);
+        }
+        set
+    }
+}
+
+impl<T: PartialOrd + Clone + Debug + Default> Debug for MultiSet<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
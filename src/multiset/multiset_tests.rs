@@ -0,0 +1,97 @@
+use crate::multiset::MultiSet;
+use proptest::prelude::*;
+use std::{format, vec::Vec};
+
+#[test]
+fn test_insert_counts_duplicates() {
+    let mut set = MultiSet::new();
+
+    assert_eq!(set.insert(2), 1);
+    assert_eq!(set.insert(2), 2);
+    assert_eq!(set.insert(1), 1);
+
+    assert_eq!(set.count(&2), 2);
+    assert_eq!(set.count(&1), 1);
+    assert_eq!(set.count(&3), 0);
+    assert_eq!(set.len(), 3);
+    assert_eq!(set.distinct_len(), 2);
+}
+
+#[test]
+fn test_remove_one() {
+    let mut set: MultiSet<i32> = [1, 1, 2].into_iter().collect();
+
+    assert!(set.remove_one(&1));
+    assert_eq!(set.count(&1), 1);
+    assert!(set.remove_one(&1));
+    assert!(!set.contains(&1));
+    assert!(!set.remove_one(&1));
+
+    assert_eq!(set.len(), 1);
+    assert_eq!(set.distinct_len(), 1);
+}
+
+#[test]
+fn test_remove_all() {
+    let mut set: MultiSet<i32> = [3, 1, 3, 3].into_iter().collect();
+
+    assert_eq!(set.remove_all(&3), 3);
+    assert_eq!(set.remove_all(&3), 0);
+
+    assert_eq!(set.len(), 1);
+    assert_eq!(set.iter().collect::<Vec<_>>(), [&1]);
+}
+
+#[test]
+fn test_pop_both_ends() {
+    let mut set: MultiSet<i32> = [2, 1, 2, 3, 1].into_iter().collect();
+
+    assert_eq!(set.first(), Some(&1));
+    assert_eq!(set.last(), Some(&3));
+    assert_eq!(set.pop_first(), Some(1));
+    assert_eq!(set.pop_first(), Some(1));
+    assert_eq!(set.pop_last(), Some(3));
+    assert_eq!(set.pop_last(), Some(2));
+    assert_eq!(set.pop_last(), Some(2));
+    assert_eq!(set.pop_last(), None);
+    assert!(set.is_empty());
+}
+
+#[test]
+fn test_iter_repeats_duplicates() {
+    let set: MultiSet<i32> = [2, 1, 2, 3, 2].into_iter().collect();
+
+    assert_eq!(set.iter().copied().collect::<Vec<_>>(), [1, 2, 2, 2, 3]);
+    assert_eq!(format!("{:?}", set), "{1, 2, 2, 2, 3}");
+}
+
+#[test]
+fn test_clear() {
+    let mut set: MultiSet<i32> = [1, 1].into_iter().collect();
+
+    set.clear();
+
+    assert!(set.is_empty());
+    assert_eq!(set.count(&1), 0);
+}
+
+proptest! {
+    #[test]
+    fn test_matches_sorted_vec(ops in prop::collection::vec((any::<u8>(), any::<bool>()), 0..200)) {
+        let mut set = MultiSet::new();
+        let mut model: Vec<u8> = Vec::new();
+        for (key, insert) in ops {
+            if insert {
+                set.insert(key);
+                let index = model.partition_point(|k| *k <= key);
+                model.insert(index, key);
+            } else {
+                let removed = model.binary_search(&key).map(|index| model.remove(index));
+                prop_assert_eq!(set.remove_one(&key), removed.is_ok());
+            }
+            prop_assert_eq!(set.len(), model.len());
+            prop_assert_eq!(set.count(&key), model.iter().filter(|k| **k == key).count());
+        }
+        prop_assert!(set.iter().eq(model.iter()));
+    }
+}
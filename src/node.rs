@@ -1,14 +1,15 @@
-use std::{
+use alloc::rc::Rc;
+use core::{
     cell::RefCell,
     fmt::{Debug, Formatter},
-    rc::Rc,
 };
 
 #[cfg(test)]
 mod node_tests;
 
+/// The color of a node in a red-black tree.
 #[derive(PartialEq, Eq, Debug, Clone)]
-pub(crate) enum NodeColor {
+pub enum NodeColor {
     Red,
     Black,
 }
@@ -21,28 +22,48 @@ pub(crate) struct Node<T> {
     pub(crate) parent: Option<Rc<RefCell<Node<T>>>>,
     pub(crate) key: T,
     pub(crate) is_sentinel: bool,
+    /// Balancing metadata for the strategies in [`crate::balance`] that do not
+    /// use colors: the subtree height in an AVL tree and the heap priority in
+    /// a treap. Unused by the red-black tree.
+    pub(crate) rank: u64,
+    /// Identifies the insertion that linked the node into a
+    /// [`Tree`](crate::tree::Tree), so that a [`Handle`](crate::tree::Handle)
+    /// can tell whether it still refers to the same element. 0 while the node
+    /// is not part of a tree.
+    pub(crate) generation: u64,
+    /// The number of nodes in the subtree rooted at this node, 0 for T.nil.
+    /// Maintained by [`Tree`](crate::tree::Tree) only.
+    pub(crate) size: usize,
 }
 
 impl<T: Default> Node<T> {
-    pub(crate) fn new(key: T) -> Self {
+    pub(crate) fn new_sentinel() -> Self {
         Self {
             left: None,
             right: None,
             color: NodeColor::Black,
             parent: None,
-            key,
-            is_sentinel: false,
+            key: T::default(),
+            is_sentinel: true,
+            rank: 0,
+            generation: 0,
+            size: 0,
         }
     }
+}
 
-    pub(crate) fn new_sentinel() -> Self {
+impl<T> Node<T> {
+    pub(crate) fn new(key: T) -> Self {
         Self {
             left: None,
             right: None,
             color: NodeColor::Black,
             parent: None,
-            key: T::default(),
-            is_sentinel: true,
+            key,
+            is_sentinel: false,
+            rank: 0,
+            generation: 0,
+            size: 1,
         }
     }
 
@@ -88,17 +109,15 @@ impl<T: Default> Node<T> {
 }
 
 impl<T: Debug> Debug for Node<T> {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        if self.left.is_some() {
-            write!(f, "{:?}", self.left.as_ref().unwrap().borrow())?;
-        }
-
-        write!(f, "key: {:?} color: {:?}", self.key, self.color)?;
-
-        if self.right.is_some() {
-            write!(f, "{:?}", self.right.as_ref().unwrap().borrow())?;
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        if self.is_sentinel {
+            return write!(f, "NIL");
         }
 
-        Ok(())
+        let color = match self.color {
+            NodeColor::Red => "R",
+            NodeColor::Black => "B",
+        };
+        write!(f, "{:?} ({})", self.key, color)
     }
 }
@@ -0,0 +1,67 @@
+use crate::tree::{Observer, Tree};
+use core::fmt::Debug;
+
+#[cfg(test)]
+mod ordered_set_tests;
+
+/// The operations shared by every balanced search tree in this crate, so that
+/// the balancing strategies can be swapped behind one interface for teaching
+/// and benchmarking.
+///
+/// Keys are unique: `insert` returns `false` and leaves the set unchanged if
+/// an equal key is already present.
+pub trait OrderedSet<T> {
+    fn insert(&mut self, key: T) -> bool;
+
+    fn delete(&mut self, key: T);
+
+    fn contains_key(&self, key: T) -> bool;
+
+    fn minimum(&self) -> Option<T>;
+
+    fn maximum(&self) -> Option<T>;
+
+    fn len(&self) -> usize;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn clear(&mut self);
+}
+
+impl<T: PartialOrd + Clone + PartialEq + Debug + Default, O: Observer<T>> OrderedSet<T>
+    for Tree<T, O>
+{
+    fn insert(&mut self, key: T) -> bool {
+        Tree::insert(self, key)
+    }
+
+    fn delete(&mut self, key: T) {
+        Tree::delete(self, key)
+    }
+
+    fn contains_key(&self, key: T) -> bool {
+        Tree::contains_key(self, key)
+    }
+
+    fn minimum(&self) -> Option<T> {
+        Tree::minimum(self)
+    }
+
+    fn maximum(&self) -> Option<T> {
+        Tree::maximum(self)
+    }
+
+    fn len(&self) -> usize {
+        Tree::len(self)
+    }
+
+    fn is_empty(&self) -> bool {
+        Tree::is_empty(self)
+    }
+
+    fn clear(&mut self) {
+        Tree::clear(self)
+    }
+}
@@ -0,0 +1,90 @@
+use crate::{
+    balance::{AvlTree, LlrbTree, ScapegoatTree, Treap},
+    ordered_set::OrderedSet,
+    top_down::TopDownTree,
+    tree::Tree,
+};
+use proptest::prelude::*;
+use std::{format, vec::Vec};
+
+#[derive(Debug, Clone)]
+enum Op {
+    Insert(u8),
+    Delete(u8),
+}
+
+fn op_strategy() -> impl Strategy<Value = Op> {
+    prop_oneof![
+        3 => any::<u8>().prop_map(Op::Insert),
+        2 => any::<u8>().prop_map(Op::Delete),
+    ]
+}
+
+/// Runs `ops` against `set` and a sorted vector, comparing every observable
+/// result of the [`OrderedSet`] interface along the way.
+fn check_against_model<S: OrderedSet<u8>>(mut set: S, ops: &[Op]) -> Result<(), TestCaseError> {
+    let mut model: Vec<u8> = Vec::new();
+    for op in ops {
+        match op {
+            Op::Insert(key) => {
+                let added = model.binary_search(key).is_err();
+                prop_assert_eq!(set.insert(*key), added);
+                if added {
+                    let index = model.partition_point(|k| k < key);
+                    model.insert(index, *key);
+                }
+            }
+            Op::Delete(key) => {
+                set.delete(*key);
+                if let Ok(index) = model.binary_search(key) {
+                    model.remove(index);
+                }
+            }
+        }
+        let key = match op {
+            Op::Insert(key) | Op::Delete(key) => *key,
+        };
+        prop_assert_eq!(set.len(), model.len());
+        prop_assert_eq!(set.is_empty(), model.is_empty());
+        prop_assert_eq!(set.contains_key(key), model.binary_search(&key).is_ok());
+        prop_assert_eq!(set.minimum(), model.first().copied());
+        prop_assert_eq!(set.maximum(), model.last().copied());
+    }
+
+    set.clear();
+    prop_assert!(set.is_empty());
+    prop_assert_eq!(set.minimum(), None);
+    Ok(())
+}
+
+proptest! {
+    #[test]
+    fn test_tree_matches_model(ops in prop::collection::vec(op_strategy(), 0..200)) {
+        check_against_model(Tree::new(), &ops)?;
+    }
+
+    #[test]
+    fn test_avl_tree_matches_model(ops in prop::collection::vec(op_strategy(), 0..200)) {
+        check_against_model(AvlTree::new(), &ops)?;
+    }
+
+    #[test]
+    fn test_llrb_tree_matches_model(ops in prop::collection::vec(op_strategy(), 0..200)) {
+        check_against_model(LlrbTree::new(), &ops)?;
+    }
+
+    #[test]
+    fn test_treap_matches_model(ops in prop::collection::vec(op_strategy(), 0..200)) {
+        check_against_model(Treap::new(), &ops)?;
+    }
+
+    #[test]
+    fn test_scapegoat_tree_matches_model(ops in prop::collection::vec(op_strategy(), 0..200)) {
+        check_against_model(ScapegoatTree::new(), &ops)?;
+    }
+
+    #[test]
+    fn test_top_down_tree_matches_model(ops in prop::collection::vec(op_strategy(), 0..200)) {
+        check_against_model(TopDownTree::new(), &ops)?;
+    }
+}
@@ -0,0 +1,274 @@
+use crate::tree::{self, Tree};
+use alloc::collections::{vec_deque, VecDeque};
+use core::{
+    cmp::Ordering,
+    fmt::{self, Debug, Formatter},
+};
+
+#[cfg(test)]
+mod quantile_tests;
+
+/// A sample together with the order in which it was pushed, which tells
+/// equal samples apart so that [`Tree`] keeps each of them.
+#[derive(Debug, Clone, Default)]
+struct Sample<T> {
+    value: T,
+    seq: u64,
+}
+
+impl<T: PartialEq> PartialEq for Sample<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value && self.seq == other.seq
+    }
+}
+
+impl<T: PartialOrd> PartialOrd for Sample<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        match self.value.partial_cmp(&other.value)? {
+            Ordering::Equal => self.seq.partial_cmp(&other.seq),
+            ordering => Some(ordering),
+        }
+    }
+}
+
+/// Keeps a collection of samples sorted in an order-statistic [`Tree`], so
+/// that any quantile is found in O(log n) by the subtree sizes.
+///
+/// Samples may repeat. Every sample is counted, so the median of
+/// `[1, 1, 1, 5]` is `1`. Samples that do not compare, such as `NaN`, must
+/// not be pushed.
+///
+/// ```
+/// use atlas_rb_tree::quantile::QuantileTracker;
+///
+/// let mut latencies: QuantileTracker<u32> = (1..=100).collect();
+/// assert_eq!(latencies.median(), Some(&50));
+/// assert_eq!(latencies.quantile(0.99), Some(&99));
+///
+/// latencies.remove(&100);
+/// assert_eq!(latencies.quantile(1.0), Some(&99));
+/// ```
+pub struct QuantileTracker<T> {
+    tree: Tree<Sample<T>>,
+    /// The sequence number of the next sample.
+    next_seq: u64,
+}
+
+/// An iterator over the samples of a [`QuantileTracker`] in ascending order.
+/// Created by [`QuantileTracker::iter`].
+pub struct Iter<'a, T> {
+    inner: tree::Iter<'a, Sample<T>>,
+}
+
+impl<T: PartialOrd + Clone + Debug + Default> QuantileTracker<T> {
+    pub fn new() -> QuantileTracker<T> {
+        Self {
+            tree: Tree::new(),
+            next_seq: 0,
+        }
+    }
+
+    /// Adds the sample `value`.
+    pub fn push(&mut self, value: T) {
+        let seq = self.next_seq;
+        self.next_seq += 1;
+        self.tree.insert(Sample { value, seq });
+    }
+
+    /// Removes one sample equal to `value`. Returns `false` if there was
+    /// none.
+    pub fn remove(&mut self, value: &T) -> bool {
+        // No sample equal to `value` orders before this one.
+        let first = Sample {
+            value: value.clone(),
+            seq: 0,
+        };
+        let index = self.tree.count_range(..first);
+        match self.tree.select(index) {
+            Some(sample) if sample.value == *value => {
+                let sample = sample.clone();
+                self.tree.delete(sample);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Returns the `q`-quantile of the samples by the nearest-rank method:
+    /// the smallest sample such that a fraction of at least `q` of all
+    /// samples is less than or equal to it. Returns `None` if there are no
+    /// samples.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `q` is not within `0.0..=1.0`.
+    pub fn quantile(&self, q: f64) -> Option<&T> {
+        assert!(
+            (0.0..=1.0).contains(&q),
+            "quantile {q} is not within 0.0..=1.0"
+        );
+        let len = self.tree.len();
+        // The nearest rank is ⌈q * len⌉, counted from 1.
+        let exact = q * len as f64;
+        let mut rank = exact as usize;
+        if (rank as f64) < exact {
+            rank += 1;
+        }
+        let index = rank.clamp(1, len.max(1)) - 1;
+        self.tree.select(index).map(|sample| &sample.value)
+    }
+
+    /// Returns the lower median of the samples, the 0.5-quantile.
+    pub fn median(&self) -> Option<&T> {
+        self.quantile(0.5)
+    }
+
+    pub fn len(&self) -> usize {
+        self.tree.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tree.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.tree.clear();
+        self.next_seq = 0;
+    }
+
+    /// Returns an iterator over the samples in ascending order.
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            inner: self.tree.iter(),
+        }
+    }
+}
+
+impl<'a, T> Iterator for Iter<'a, T>
+where
+    T: PartialOrd + Clone + Debug + Default,
+{
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        self.inner.next().map(|sample| &sample.value)
+    }
+}
+
+impl<T: PartialOrd + Clone + Debug + Default> Default for QuantileTracker<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: PartialOrd + Clone + Debug + Default> FromIterator<T> for QuantileTracker<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut tracker = QuantileTracker::new();
+        for value in iter {
+            tracker.push(value);
+        }
+        tracker
+    }
+}
+
+impl<T: PartialOrd + Clone + Debug + Default> Debug for QuantileTracker<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+/// The quantiles of the last `capacity` samples pushed. Once the window is
+/// full, every new sample evicts the oldest one.
+///
+/// ```
+/// use atlas_rb_tree::quantile::SlidingWindow;
+///
+/// let mut window = SlidingWindow::new(3);
+/// for sample in [10, 20, 30] {
+///     window.push(sample);
+/// }
+/// assert_eq!(window.median(), Some(&20));
+///
+/// assert_eq!(window.push(40), Some(10));
+/// assert_eq!(window.median(), Some(&30));
+/// ```
+pub struct SlidingWindow<T> {
+    /// The samples in the window in the order they were pushed.
+    samples: VecDeque<T>,
+    tracker: QuantileTracker<T>,
+    capacity: usize,
+}
+
+impl<T: PartialOrd + Clone + Debug + Default> SlidingWindow<T> {
+    /// Creates an empty window of `capacity` samples.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` is 0.
+    pub fn new(capacity: usize) -> SlidingWindow<T> {
+        assert!(capacity > 0, "a sliding window needs room for a sample");
+        Self {
+            samples: VecDeque::with_capacity(capacity),
+            tracker: QuantileTracker::new(),
+            capacity,
+        }
+    }
+
+    /// Adds the sample `value` and returns the oldest sample if it was
+    /// evicted to make room.
+    pub fn push(&mut self, value: T) -> Option<T> {
+        let evicted = if self.samples.len() == self.capacity {
+            let oldest = self.samples.pop_front()?;
+            self.tracker.remove(&oldest);
+            Some(oldest)
+        } else {
+            None
+        };
+        self.samples.push_back(value.clone());
+        self.tracker.push(value);
+        evicted
+    }
+
+    /// Returns the `q`-quantile of the samples in the window, like
+    /// [`QuantileTracker::quantile`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `q` is not within `0.0..=1.0`.
+    pub fn quantile(&self, q: f64) -> Option<&T> {
+        self.tracker.quantile(q)
+    }
+
+    /// Returns the lower median of the samples in the window.
+    pub fn median(&self) -> Option<&T> {
+        self.tracker.median()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn len(&self) -> usize {
+        self.samples.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.samples.clear();
+        self.tracker.clear();
+    }
+
+    /// Returns an iterator over the samples in the window, oldest first.
+    pub fn iter(&self) -> vec_deque::Iter<'_, T> {
+        self.samples.iter()
+    }
+}
+
+impl<T: PartialOrd + Clone + Debug + Default> Debug for SlidingWindow<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
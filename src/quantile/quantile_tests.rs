@@ -0,0 +1,101 @@
+use crate::quantile::{QuantileTracker, SlidingWindow};
+use proptest::prelude::*;
+use std::{collections::VecDeque, format, vec::Vec};
+
+#[test]
+fn test_quantiles() {
+    let tracker: QuantileTracker<i32> = (1..=100).rev().collect();
+
+    assert_eq!(tracker.quantile(0.0), Some(&1));
+    assert_eq!(tracker.quantile(0.25), Some(&25));
+    assert_eq!(tracker.median(), Some(&50));
+    assert_eq!(tracker.quantile(0.99), Some(&99));
+    assert_eq!(tracker.quantile(0.995), Some(&100));
+    assert_eq!(tracker.quantile(1.0), Some(&100));
+}
+
+#[test]
+fn test_empty() {
+    let tracker = QuantileTracker::<i32>::new();
+
+    assert_eq!(tracker.median(), None);
+    assert_eq!(tracker.quantile(1.0), None);
+}
+
+#[test]
+fn test_duplicates() {
+    let mut tracker: QuantileTracker<i32> = [5, 1, 1, 1].into_iter().collect();
+
+    assert_eq!(tracker.len(), 4);
+    assert_eq!(tracker.median(), Some(&1));
+    assert_eq!(tracker.quantile(0.76), Some(&5));
+
+    assert!(tracker.remove(&1));
+    assert!(tracker.remove(&1));
+    assert_eq!(tracker.median(), Some(&1));
+    assert!(tracker.remove(&1));
+    assert!(!tracker.remove(&1));
+    assert_eq!(tracker.median(), Some(&5));
+    assert!(tracker.iter().eq([5].iter()));
+}
+
+#[test]
+#[should_panic(expected = "is not within 0.0..=1.0")]
+fn test_quantile_out_of_range() {
+    let tracker: QuantileTracker<i32> = (0..10).collect();
+    tracker.quantile(1.5);
+}
+
+#[test]
+fn test_sliding_window_evicts_oldest() {
+    let mut window = SlidingWindow::new(3);
+
+    assert_eq!(window.push(7), None);
+    assert_eq!(window.push(7), None);
+    assert_eq!(window.push(1), None);
+    assert_eq!(window.median(), Some(&7));
+
+    assert_eq!(window.push(2), Some(7));
+    assert_eq!(window.median(), Some(&2));
+    assert_eq!(window.push(3), Some(7));
+    assert_eq!(window.median(), Some(&2));
+    assert_eq!(window.len(), 3);
+    assert!(window.iter().eq([1, 2, 3].iter()));
+}
+
+#[test]
+fn test_debug() {
+    let tracker: QuantileTracker<i32> = [3, 1, 3].into_iter().collect();
+    let window: SlidingWindow<i32> = {
+        let mut window = SlidingWindow::new(2);
+        window.push(3);
+        window.push(1);
+        window
+    };
+
+    assert_eq!(format!("{:?}", tracker), "[1, 3, 3]");
+    assert_eq!(format!("{:?}", window), "[3, 1]");
+}
+
+proptest! {
+    #[test]
+    fn test_window_matches_sorted_vec(
+        samples in prop::collection::vec(0u8..20, 1..200),
+        capacity in 1usize..20,
+        q in 0.0f64..=1.0,
+    ) {
+        let mut window = SlidingWindow::new(capacity);
+        let mut model = VecDeque::new();
+        for sample in samples {
+            model.push_back(sample);
+            let evicted = (model.len() > capacity).then(|| model.pop_front().unwrap());
+            prop_assert_eq!(window.push(sample), evicted);
+
+            let mut sorted: Vec<_> = model.iter().copied().collect();
+            sorted.sort();
+            let rank = (q * sorted.len() as f64).ceil() as usize;
+            prop_assert_eq!(window.quantile(q), Some(&sorted[rank.max(1) - 1]));
+            prop_assert_eq!(window.tracker.tree.validate(), Ok(()));
+        }
+    }
+}
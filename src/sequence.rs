@@ -0,0 +1,199 @@
+use crate::tree::{self, Tree};
+use core::{
+    cmp::Ordering,
+    fmt::{self, Debug, Formatter},
+};
+
+#[cfg(test)]
+mod sequence_tests;
+
+/// A list backed by a red-black [`Tree`] that is ordered by position instead
+/// of by comparing elements.
+///
+/// Every node stores the size of its subtree, so the position of a node is
+/// the number of nodes before it and serves as an implicit key. Accessing,
+/// inserting and removing at any index takes `O(log n)`, while splitting and
+/// concatenating also take time linear in the smaller piece. The elements are
+/// never compared, so they need neither an order nor a default value.
+///
+/// ```
+/// use atlas_rb_tree::sequence::Sequence;
+///
+/// let mut letters: Sequence<char> = "acd".chars().collect();
+/// letters.insert_at(1, 'b');
+///
+/// let (front, back) = letters.split_at(2);
+/// assert_eq!(front.iter().collect::<String>(), "ab");
+/// assert_eq!(back.concat(front).iter().collect::<String>(), "cdab");
+/// ```
+pub struct Sequence<T> {
+    tree: Tree<Item<T>>,
+}
+
+/// An element in a node of a [`Sequence`], which gives the tree the key
+/// traits it requires without asking them of `T`. Only T.nil holds `None`.
+/// Items are never compared, so they all compare equal.
+#[derive(Clone)]
+struct Item<T>(Option<T>);
+
+impl<T> Item<T> {
+    fn get(&self) -> &T {
+        self.0.as_ref().expect("only T.nil holds no element")
+    }
+
+    fn get_mut(&mut self) -> &mut T {
+        self.0.as_mut().expect("only T.nil holds no element")
+    }
+}
+
+impl<T> Default for Item<T> {
+    fn default() -> Self {
+        Item(None)
+    }
+}
+
+impl<T> PartialEq for Item<T> {
+    fn eq(&self, _: &Self) -> bool {
+        true
+    }
+}
+
+impl<T> PartialOrd for Item<T> {
+    fn partial_cmp(&self, _: &Self) -> Option<Ordering> {
+        Some(Ordering::Equal)
+    }
+}
+
+impl<T> Debug for Item<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str("Item")
+    }
+}
+
+/// An iterator over the elements of a [`Sequence`] in order. Created by
+/// [`Sequence::iter`].
+pub struct Iter<'a, T> {
+    inner: tree::Iter<'a, Item<T>>,
+}
+
+impl<T: Clone> Sequence<T> {
+    pub fn new() -> Sequence<T> {
+        Self { tree: Tree::new() }
+    }
+
+    /// Returns the element at `index`, or `None` if `index` is out of bounds.
+    pub fn get(&self, index: usize) -> Option<&T> {
+        let node = self.tree.node_at(index)?;
+        Some(self.tree.key_ref(&node).get())
+    }
+
+    /// Returns the element at `index` mutably, or `None` if `index` is out of
+    /// bounds.
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        let node = self.tree.node_at(index)?;
+        Some(self.tree.key_mut(&node).get_mut())
+    }
+
+    /// Inserts `value` at `index`, shifting the elements after it back.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is greater than the length.
+    pub fn insert_at(&mut self, index: usize, value: T) {
+        assert!(
+            index <= self.len(),
+            "insertion index {index} is out of bounds for length {}",
+            self.len()
+        );
+        self.tree.insert_at_index(index, Item(Some(value)));
+    }
+
+    /// Appends `value` after the last element.
+    pub fn push(&mut self, value: T) {
+        self.tree.insert_at_index(self.len(), Item(Some(value)));
+    }
+
+    /// Removes and returns the element at `index`, or `None` if `index` is out
+    /// of bounds.
+    pub fn remove_at(&mut self, index: usize) -> Option<T> {
+        self.tree.remove_at_index(index)?.0
+    }
+
+    /// Splits the sequence into the elements before `index` and the elements
+    /// from `index` on.
+    ///
+    /// Takes `O(log² n)` plus `O(min(index, n - index))` time, because the
+    /// leaves of the smaller piece are relinked to a T.nil of their own.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is greater than the length.
+    pub fn split_at(mut self, index: usize) -> (Sequence<T>, Sequence<T>) {
+        assert!(
+            index <= self.len(),
+            "split index {index} is out of bounds for length {}",
+            self.len()
+        );
+        let back = self.tree.split_off_at(index);
+        (self, Sequence { tree: back })
+    }
+
+    /// Returns the elements of the sequence followed by those of `other`.
+    ///
+    /// Takes `O(log n)` plus `O(min(n, m))` time for sequences of `n` and `m`
+    /// elements, because the leaves of the shorter one are relinked to the
+    /// T.nil of the other.
+    pub fn concat(mut self, other: Sequence<T>) -> Sequence<T> {
+        self.tree.concat(other.tree);
+        self
+    }
+
+    /// Returns an iterator over the elements in order.
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            inner: self.tree.iter(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.tree.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tree.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.tree.clear();
+    }
+}
+
+impl<'a, T: Clone> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        self.inner.next().map(Item::get)
+    }
+}
+
+impl<T: Clone> Default for Sequence<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Clone> FromIterator<T> for Sequence<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut sequence = Sequence::new();
+        for value in iter {
+            sequence.push(value);
+        }
+        sequence
+    }
+}
+
+impl<T: Clone + Debug> Debug for Sequence<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
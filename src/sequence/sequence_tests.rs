@@ -0,0 +1,196 @@
+use crate::sequence::Sequence;
+use core::fmt::Debug;
+use proptest::prelude::*;
+use std::{format, vec, vec::Vec};
+
+fn assert_valid<T: Clone>(sequence: &Sequence<T>) {
+    assert_eq!(sequence.tree.validate_structure(), Ok(()));
+}
+
+#[test]
+fn test_insert_at_and_get() {
+    let mut sequence = Sequence::new();
+    sequence.insert_at(0, 'b');
+    sequence.insert_at(0, 'a');
+    sequence.insert_at(2, 'd');
+    sequence.insert_at(2, 'c');
+
+    assert_eq!(sequence.len(), 4);
+    assert_eq!(
+        sequence.iter().copied().collect::<Vec<_>>(),
+        ['a', 'b', 'c', 'd']
+    );
+    assert_eq!(sequence.get(2), Some(&'c'));
+    assert_eq!(sequence.get(4), None);
+    assert_valid(&sequence);
+}
+
+#[test]
+fn test_elements_need_not_be_sorted() {
+    let sequence: Sequence<i32> = [3, 1, 3, 2].into_iter().collect();
+
+    assert_eq!(sequence.iter().copied().collect::<Vec<_>>(), [3, 1, 3, 2]);
+    assert_valid(&sequence);
+}
+
+#[test]
+fn test_elements_need_no_order_or_default() {
+    /// Neither ordered nor defaultable.
+    #[derive(Debug, Clone, PartialEq)]
+    struct Task(&'static str);
+
+    let mut sequence: Sequence<Task> = [Task("build"), Task("test")].into_iter().collect();
+    sequence.insert_at(1, Task("lint"));
+
+    assert_eq!(sequence.remove_at(0), Some(Task("build")));
+    assert_eq!(sequence.get(1), Some(&Task("test")));
+    assert_eq!(format!("{:?}", sequence), r#"[Task("lint"), Task("test")]"#);
+    assert_valid(&sequence);
+}
+
+#[test]
+#[should_panic(expected = "out of bounds")]
+fn test_insert_at_out_of_bounds() {
+    let mut sequence = Sequence::new();
+    sequence.insert_at(1, 0);
+}
+
+#[test]
+fn test_remove_at() {
+    let mut sequence: Sequence<i32> = (0..10).collect();
+
+    assert_eq!(sequence.remove_at(3), Some(3));
+    assert_eq!(sequence.remove_at(9), None);
+    assert_eq!(sequence.get(3), Some(&4));
+    assert_eq!(sequence.len(), 9);
+    assert_valid(&sequence);
+}
+
+#[test]
+fn test_get_mut() {
+    let mut sequence: Sequence<i32> = (0..3).collect();
+    *sequence.get_mut(1).unwrap() = 10;
+
+    assert_eq!(format!("{:?}", sequence), "[0, 10, 2]");
+}
+
+#[test]
+fn test_split_at() {
+    let sequence: Sequence<i32> = (0..100).collect();
+    let (front, back) = sequence.split_at(37);
+
+    assert_eq!(
+        front.iter().copied().collect::<Vec<_>>(),
+        (0..37).collect::<Vec<_>>()
+    );
+    assert_eq!(
+        back.iter().copied().collect::<Vec<_>>(),
+        (37..100).collect::<Vec<_>>()
+    );
+    assert_valid(&front);
+    assert_valid(&back);
+}
+
+#[test]
+fn test_split_at_ends() {
+    let sequence: Sequence<i32> = (0..10).collect();
+    let (empty, sequence) = sequence.split_at(0);
+    assert!(empty.is_empty());
+    let (sequence, empty) = sequence.split_at(10);
+    assert!(empty.is_empty());
+
+    assert_eq!(sequence.len(), 10);
+    assert_valid(&sequence);
+}
+
+#[test]
+fn test_concat_split_pieces() {
+    let sequence: Sequence<i32> = (0..50).collect();
+    let (front, back) = sequence.split_at(20);
+    let mut sequence = back.concat(front);
+
+    let expected: Vec<_> = (20..50).chain(0..20).collect();
+    assert_eq!(sequence.iter().copied().collect::<Vec<_>>(), expected);
+    assert_valid(&sequence);
+
+    sequence.insert_at(30, -1);
+    assert_eq!(sequence.remove_at(0), Some(20));
+    assert_eq!(sequence.get(29), Some(&-1));
+    assert_valid(&sequence);
+}
+
+#[test]
+fn test_concat_independent_sequences() {
+    let short: Sequence<i32> = (0..3).collect();
+    let long: Sequence<i32> = (3..40).collect();
+
+    let sequence = short.concat(long);
+    assert_eq!(
+        sequence.iter().copied().collect::<Vec<_>>(),
+        (0..40).collect::<Vec<_>>()
+    );
+    assert_valid(&sequence);
+
+    let sequence = sequence.concat((40..45).collect());
+    assert_eq!(sequence.len(), 45);
+    assert_eq!(sequence.get(44), Some(&44));
+    assert_valid(&sequence);
+}
+
+#[test]
+fn test_concat_empty() {
+    let sequence: Sequence<i32> = (0..5).collect();
+    let sequence = Sequence::new().concat(sequence).concat(Sequence::new());
+
+    assert_eq!(
+        sequence.iter().copied().collect::<Vec<_>>(),
+        [0, 1, 2, 3, 4]
+    );
+    assert_valid(&sequence);
+}
+
+#[derive(Debug, Clone)]
+enum Op {
+    Insert(usize, u8),
+    Remove(usize),
+    Rotate(usize),
+}
+
+fn op() -> impl Strategy<Value = Op> {
+    prop_oneof![
+        (any::<usize>(), any::<u8>()).prop_map(|(index, value)| Op::Insert(index, value)),
+        any::<usize>().prop_map(Op::Remove),
+        any::<usize>().prop_map(Op::Rotate),
+    ]
+}
+
+proptest! {
+    #[test]
+    fn test_matches_vec(ops in prop::collection::vec(op(), 0..200)) {
+        let mut sequence = Sequence::new();
+        let mut model = vec![];
+        for op in ops {
+            match op {
+                Op::Insert(index, value) => {
+                    let index = index % (model.len() + 1);
+                    sequence.insert_at(index, value);
+                    model.insert(index, value);
+                }
+                Op::Remove(index) => {
+                    let index = index % (model.len() + 1);
+                    let expected = (index < model.len()).then(|| model.remove(index));
+                    prop_assert_eq!(sequence.remove_at(index), expected);
+                }
+                Op::Rotate(index) => {
+                    let index = index % (model.len() + 1);
+                    let (front, back) = sequence.split_at(index);
+                    sequence = back.concat(front);
+                    model.rotate_left(index);
+                }
+            }
+            prop_assert_eq!(sequence.len(), model.len());
+            prop_assert_eq!(sequence.tree.validate_structure(), Ok(()));
+        }
+        prop_assert!(sequence.iter().eq(model.iter()));
+    }
+}
@@ -0,0 +1,298 @@
+//! A red-black tree that rebalances on the way down.
+//!
+//! [`Tree`] follows CLRS: it inserts or deletes at the bottom and then walks
+//! back up through parent pointers to restore the red-black properties. The
+//! single-pass algorithms here, as described by Julienne Walker, split 4-nodes
+//! on the way down during insertion and push a red node down ahead of the
+//! search during deletion, so that the final change at the bottom never
+//! violates the properties. Nothing ever walks back up, so nodes have no
+//! parent pointers: they live in a `Vec` and link to their children by index,
+//! with no `Rc` cycles to break.
+//!
+//! [`Tree`]: crate::tree::Tree
+
+use crate::ordered_set::OrderedSet;
+use alloc::{vec, vec::Vec};
+use core::{fmt::Debug, mem};
+
+#[cfg(test)]
+mod top_down_tests;
+
+/// Index of the black leaf that stands in for every missing child, like T.nil
+/// in [`Tree`](crate::tree::Tree). It is never written to.
+const NIL: usize = 0;
+
+/// Index of the dummy node above the root. The root is its right child, so
+/// that rotations at the root need no special case.
+const HEAD: usize = 1;
+
+struct Slot<T> {
+    key: T,
+    red: bool,
+    /// The left and right children, indexed by direction so that the mirrored
+    /// cases share one code path.
+    link: [usize; 2],
+}
+
+impl<T> Slot<T> {
+    fn new(key: T) -> Self {
+        Self {
+            key,
+            red: true,
+            link: [NIL, NIL],
+        }
+    }
+}
+
+/// A red-black tree with top-down insertion and deletion, implementing the
+/// same [`OrderedSet`] interface as [`Tree`](crate::tree::Tree).
+pub struct TopDownTree<T> {
+    slots: Vec<Slot<T>>,
+    /// Slots of deleted nodes, reused by later insertions.
+    free: Vec<usize>,
+    length: usize,
+}
+
+impl<T> TopDownTree<T> {
+    fn root(&self) -> usize {
+        self.slots[HEAD].link[1]
+    }
+
+    fn is_red(&self, node: usize) -> bool {
+        self.slots[node].red
+    }
+}
+
+impl<T: PartialOrd + Clone + PartialEq + Debug + Default> TopDownTree<T> {
+    pub fn new() -> TopDownTree<T> {
+        let mut nil = Slot::new(T::default());
+        nil.red = false;
+        let mut head = Slot::new(T::default());
+        head.red = false;
+        Self {
+            slots: vec![nil, head],
+            free: vec![],
+            length: 0,
+        }
+    }
+
+    fn allocate(&mut self, key: T) -> usize {
+        match self.free.pop() {
+            Some(index) => {
+                self.slots[index] = Slot::new(key);
+                index
+            }
+            None => {
+                self.slots.push(Slot::new(key));
+                self.slots.len() - 1
+            }
+        }
+    }
+
+    /// Rotates `root` away from `dir` and returns the new root of the
+    /// subtree, which is colored black above a red `root`.
+    fn single(&mut self, root: usize, dir: usize) -> usize {
+        let save = self.slots[root].link[1 - dir];
+        self.slots[root].link[1 - dir] = self.slots[save].link[dir];
+        self.slots[save].link[dir] = root;
+        self.slots[root].red = true;
+        self.slots[save].red = false;
+        save
+    }
+
+    /// Rotates the child of `root` opposite `dir` the other way first, then
+    /// `root` itself.
+    fn double(&mut self, root: usize, dir: usize) -> usize {
+        let child = self.slots[root].link[1 - dir];
+        self.slots[root].link[1 - dir] = self.single(child, 1 - dir);
+        self.single(root, dir)
+    }
+
+    fn search(&self, key: &T) -> Option<usize> {
+        let mut node = self.root();
+        while node != NIL {
+            let slot = &self.slots[node];
+            if *key == slot.key {
+                return Some(node);
+            }
+            node = slot.link[(slot.key < *key) as usize];
+        }
+        None
+    }
+}
+
+impl<T: PartialOrd + Clone + PartialEq + Debug + Default> OrderedSet<T> for TopDownTree<T> {
+    fn insert(&mut self, key: T) -> bool {
+        if self.root() == NIL {
+            let root = self.allocate(key);
+            self.slots[HEAD].link[1] = root;
+            self.slots[root].red = false;
+            self.length += 1;
+            return true;
+        }
+
+        // Great-grandparent, grandparent, parent and current node.
+        let (mut t, mut g, mut p) = (HEAD, NIL, NIL);
+        let mut q = self.root();
+        let (mut dir, mut last) = (0, 0);
+        let mut inserted = false;
+        loop {
+            if q == NIL {
+                q = self.allocate(key.clone());
+                self.slots[p].link[dir] = q;
+                inserted = true;
+            } else if self.is_red(self.slots[q].link[0]) && self.is_red(self.slots[q].link[1]) {
+                // Split the 4-node before passing through it.
+                self.slots[q].red = true;
+                let [left, right] = self.slots[q].link;
+                self.slots[left].red = false;
+                self.slots[right].red = false;
+            }
+
+            // Fix a red violation between q and its parent.
+            if self.is_red(q) && self.is_red(p) {
+                let dir2 = (self.slots[t].link[1] == g) as usize;
+                self.slots[t].link[dir2] = if q == self.slots[p].link[last] {
+                    self.single(g, 1 - last)
+                } else {
+                    self.double(g, 1 - last)
+                };
+            }
+
+            // An equal key ends the descent. The 4-nodes split on the way
+            // down are still valid.
+            if inserted || self.slots[q].key == key {
+                break;
+            }
+
+            last = dir;
+            dir = (self.slots[q].key < key) as usize;
+            if g != NIL {
+                t = g;
+            }
+            g = p;
+            p = q;
+            q = self.slots[q].link[dir];
+        }
+
+        if inserted {
+            self.length += 1;
+        }
+        let root = self.root();
+        self.slots[root].red = false;
+        inserted
+    }
+
+    fn delete(&mut self, key: T) {
+        // Parent and current node, plus the node holding `key`.
+        let (mut p, mut q) = (NIL, HEAD);
+        let mut found = NIL;
+        let mut dir = 1;
+        while self.slots[q].link[dir] != NIL {
+            let last = dir;
+            let g = p;
+            p = q;
+            q = self.slots[q].link[dir];
+            dir = (self.slots[q].key < key) as usize;
+            if self.slots[q].key == key {
+                found = q;
+            }
+
+            // Push a red node down so that q ends up red or with a red child
+            // in the search direction.
+            if self.is_red(q) || self.is_red(self.slots[q].link[dir]) {
+                continue;
+            }
+            if self.is_red(self.slots[q].link[1 - dir]) {
+                let rotated = self.single(q, dir);
+                self.slots[p].link[last] = rotated;
+                p = rotated;
+                continue;
+            }
+            let s = self.slots[p].link[1 - last];
+            if s == NIL {
+                continue;
+            }
+            if !self.is_red(self.slots[s].link[0]) && !self.is_red(self.slots[s].link[1]) {
+                // Merge p, q and the sibling into a 4-node.
+                self.slots[p].red = false;
+                self.slots[s].red = true;
+                self.slots[q].red = true;
+            } else {
+                let dir2 = (self.slots[g].link[1] == p) as usize;
+                let top = if self.is_red(self.slots[s].link[last]) {
+                    self.double(p, last)
+                } else {
+                    self.single(p, last)
+                };
+                self.slots[g].link[dir2] = top;
+                self.slots[q].red = true;
+                self.slots[top].red = true;
+                let [left, right] = self.slots[top].link;
+                self.slots[left].red = false;
+                self.slots[right].red = false;
+            }
+        }
+
+        // q has at most one child now. Move its key into the node being
+        // deleted and splice q out in its place. Without such a node the
+        // recolorings and rotations above still leave a valid tree.
+        if found != NIL {
+            let child = self.slots[q].link[(self.slots[q].link[0] == NIL) as usize];
+            let side = (self.slots[p].link[1] == q) as usize;
+            self.slots[p].link[side] = child;
+            let removed = mem::take(&mut self.slots[q].key);
+            if found != q {
+                self.slots[found].key = removed;
+            }
+            self.free.push(q);
+            self.length -= 1;
+        }
+
+        let root = self.root();
+        self.slots[root].red = false;
+    }
+
+    fn contains_key(&self, key: T) -> bool {
+        self.search(&key).is_some()
+    }
+
+    fn minimum(&self) -> Option<T> {
+        let mut node = self.root();
+        if node == NIL {
+            return None;
+        }
+        while self.slots[node].link[0] != NIL {
+            node = self.slots[node].link[0];
+        }
+        Some(self.slots[node].key.clone())
+    }
+
+    fn maximum(&self) -> Option<T> {
+        let mut node = self.root();
+        if node == NIL {
+            return None;
+        }
+        while self.slots[node].link[1] != NIL {
+            node = self.slots[node].link[1];
+        }
+        Some(self.slots[node].key.clone())
+    }
+
+    fn len(&self) -> usize {
+        self.length
+    }
+
+    fn clear(&mut self) {
+        self.slots.truncate(HEAD + 1);
+        self.slots[HEAD].link = [NIL, NIL];
+        self.free.clear();
+        self.length = 0;
+    }
+}
+
+impl<T: PartialOrd + Clone + PartialEq + Debug + Default> Default for TopDownTree<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,143 @@
+use crate::{
+    ordered_set::OrderedSet,
+    top_down::{TopDownTree, HEAD, NIL},
+};
+use proptest::prelude::*;
+use std::{collections::BTreeSet, format, vec::Vec};
+
+/// Checks the red-black properties and the search order below `node`,
+/// returning its black height.
+fn check_red_black<T: PartialOrd>(tree: &TopDownTree<T>, node: usize) -> usize {
+    if node == NIL {
+        return 1;
+    }
+    let slot = &tree.slots[node];
+    let [left, right] = slot.link;
+    if slot.red {
+        assert!(
+            !tree.slots[left].red && !tree.slots[right].red,
+            "Red node has a red child"
+        );
+    }
+    if left != NIL {
+        assert!(
+            tree.slots[left].key <= slot.key,
+            "Left child is out of order"
+        );
+    }
+    if right != NIL {
+        assert!(
+            tree.slots[right].key >= slot.key,
+            "Right child is out of order"
+        );
+    }
+    let left_height = check_red_black(tree, left);
+    let right_height = check_red_black(tree, right);
+    assert_eq!(left_height, right_height, "Black heights differ");
+    left_height + !slot.red as usize
+}
+
+fn assert_red_black<T: PartialOrd>(tree: &TopDownTree<T>) {
+    assert!(!tree.slots[NIL].red, "T.nil must be black");
+    assert!(!tree.slots[tree.root()].red, "Root must be black");
+    assert_eq!(tree.slots[HEAD].link[0], NIL);
+    check_red_black(tree, tree.root());
+}
+
+#[test]
+fn test_sequential_inserts_keep_properties() {
+    let mut tree = TopDownTree::new();
+    for key in 0..500 {
+        tree.insert(key);
+        assert_red_black(&tree);
+    }
+
+    assert_eq!(tree.len(), 500);
+    assert_eq!(tree.minimum(), Some(0));
+    assert_eq!(tree.maximum(), Some(499));
+}
+
+#[test]
+fn test_delete_every_key() {
+    let mut tree = TopDownTree::new();
+    for key in 0..100 {
+        tree.insert((key * 37) % 100);
+    }
+    for key in 0..100 {
+        tree.delete(key);
+        assert_red_black(&tree);
+        assert!(!tree.contains_key(key));
+    }
+
+    assert!(tree.is_empty());
+    assert_eq!(tree.root(), NIL);
+}
+
+#[test]
+fn test_deleted_slots_are_reused() {
+    let mut tree = TopDownTree::new();
+    for key in 0..10 {
+        tree.insert(key);
+    }
+    let slots = tree.slots.len();
+    for key in 0..10 {
+        tree.delete(key);
+        tree.insert(key + 10);
+    }
+
+    assert_eq!(tree.slots.len(), slots);
+    assert_eq!(tree.minimum(), Some(10));
+}
+
+#[test]
+fn test_duplicates_and_missing_keys_change_nothing() {
+    let mut tree = TopDownTree::new();
+    for key in 0..50 {
+        tree.insert(key * 2);
+    }
+    let slots = tree.slots.len();
+
+    for key in 0..50 {
+        assert!(!tree.insert(key * 2));
+        tree.delete(key * 2 + 1);
+        assert_red_black(&tree);
+    }
+    assert_eq!(tree.len(), 50);
+    assert_eq!(tree.slots.len(), slots);
+    assert!((0..50).all(|key| tree.contains_key(key * 2)));
+}
+
+#[test]
+fn test_clear_releases_slots() {
+    let mut tree = TopDownTree::new();
+    for key in 0..10 {
+        tree.insert(key);
+    }
+    tree.clear();
+
+    assert!(tree.is_empty());
+    assert_eq!(tree.slots.len(), HEAD + 1);
+    tree.insert(1);
+    assert_red_black(&tree);
+}
+
+proptest! {
+    #[test]
+    fn test_random_operations_keep_properties(keys in prop::collection::vec(any::<u8>(), 0..200)) {
+        let mut tree = TopDownTree::new();
+        for key in &keys {
+            tree.insert(*key);
+            assert_red_black(&tree);
+        }
+        let removals: Vec<u8> = keys.iter().step_by(2).copied().collect();
+        for key in removals {
+            tree.delete(key);
+            assert_red_black(&tree);
+        }
+        let mut remaining: BTreeSet<u8> = keys.iter().copied().collect();
+        for key in keys.iter().step_by(2) {
+            remaining.remove(key);
+        }
+        prop_assert_eq!(tree.len(), remaining.len());
+    }
+}
@@ -1,38 +1,132 @@
-use crate::node::{Node, NodeColor};
-use std::{
-    cell::RefCell,
-    fmt::{Debug, Formatter},
-    rc::Rc,
-};
+use crate::{bst, node::Node};
+use alloc::{rc::Rc, vec::Vec};
+use core::{cell::RefCell, fmt::Debug};
+
+mod augment;
+mod cursor;
+mod dot;
+mod extract;
+mod finger;
+mod handle;
+mod join;
+mod observer;
+mod order;
+mod render;
+mod reserve;
+mod search;
+mod shape;
+mod stats;
+mod trace;
+mod traverse;
+
+pub use crate::node::NodeColor;
+pub use cursor::{Cursor, CursorMut, UnorderedKeyError};
+pub use dot::DotOptions;
+pub use extract::{Drain, ExtractIf};
+pub use handle::Handle;
+pub use observer::{NoopObserver, Observer, RebalanceCounts};
+pub use render::Render;
+pub use shape::{ParseShapeError, Shape};
+pub use stats::{TreeStats, ValidationError};
+pub use trace::{Frame, Step, Tracer};
+pub use traverse::{Iter, LevelOrder, NodeView, PostOrder, PreOrder};
+
+pub(crate) use augment::{Augmentation, Children};
+pub(crate) use search::{compare, Position};
 
 #[cfg(test)]
-mod tree_tests;
+pub(crate) mod tree_tests;
 
-pub struct Tree<T> {
+/// A red-black tree. `O` is notified of the rebalancing work, see
+/// [`Observer`].
+pub struct Tree<T, O = NoopObserver> {
     root: Rc<RefCell<Node<T>>>,
     sentinel: Rc<RefCell<Node<T>>>,
     length: usize,
+    /// Nodes allocated ahead of time by `Tree::try_reserve`, used up by later
+    /// insertions before allocating new ones.
+    spare: Vec<Rc<RefCell<Node<T>>>>,
+    /// The generation stamped on the most recently inserted node.
+    generation: u64,
+    /// Keeps subtree summaries in the keys, for wrappers that need them.
+    augmentation: Option<Augmentation<T>>,
+    observer: O,
 }
 
-// TODO cleanup traits. For instance, debug might be to strict.
 impl<T: PartialOrd + Clone + PartialEq + Debug + Default> Tree<T> {
     pub fn new() -> Tree<T> {
+        Self::with_observer(NoopObserver)
+    }
+}
+
+// TODO cleanup traits. For instance, debug might be to strict.
+impl<T: PartialOrd + Clone + PartialEq + Debug + Default, O: Observer<T>> Tree<T, O> {
+    /// Creates an empty tree that notifies `observer` of its rebalancing work.
+    pub fn with_observer(observer: O) -> Tree<T, O> {
         let sentinel = Rc::new(RefCell::new(Node::new_sentinel()));
         Self {
             root: sentinel.clone(),
             sentinel,
             length: 0,
+            spare: Vec::new(),
+            generation: 0,
+            augmentation: None,
+            observer,
+        }
+    }
+
+    pub fn observer(&self) -> &O {
+        &self.observer
+    }
+
+    pub fn observer_mut(&mut self) -> &mut O {
+        &mut self.observer
+    }
+
+    /// Adds `key` to the tree. Returns `false` and leaves the tree unchanged
+    /// if an equal key is already present.
+    pub fn insert(&mut self, key: T) -> bool {
+        let Ok(z) = self.insert_node(key) else {
+            return false;
+        };
+        self.check_invariants("insert", Some(&z.borrow().key));
+        true
+    }
+
+    /// Adds `key` to the tree, replacing an equal key if one is present, and
+    /// returns the replaced key. The replacement keeps its node, so handles
+    /// to it stay valid.
+    pub fn replace(&mut self, key: T) -> Option<T> {
+        match self.insert_node(key) {
+            Ok(z) => {
+                self.check_invariants("replace", Some(&z.borrow().key));
+                None
+            }
+            Err((node, key)) => {
+                let old = core::mem::replace(&mut node.borrow_mut().key, key);
+                self.update_path(&node);
+                self.check_invariants("replace", Some(&node.borrow().key));
+                Some(old)
+            }
         }
     }
 
-    pub fn insert(&mut self, key: T) {
-        let mut z = Node::new(key);
+    /// Inserts `key` below the leaf its search ends at and returns the new
+    /// node. If the search finds an equal key instead, returns its node
+    /// together with `key`.
+    #[allow(clippy::type_complexity)]
+    fn insert_node(&mut self, key: T) -> Result<Rc<RefCell<Node<T>>>, (Rc<RefCell<Node<T>>>, T)> {
         let mut x = self.root.clone();
         let mut y = self.sentinel.clone();
+        let mut as_left = false;
 
         while !x.borrow().is_nil() {
+            if key == x.borrow().key {
+                return Err((x, key));
+            }
             y = x.clone();
-            if z.key < x.borrow().key {
+            as_left = key < x.borrow().key;
+            if as_left {
                 let x_tmp = x.borrow().left().clone();
                 x = x_tmp
             } else {
@@ -40,13 +134,25 @@ impl<T: PartialOrd + Clone + PartialEq + Debug + Default> Tree<T> {
                 x = x_tmp;
             }
         }
-        z.set_parent(y.clone());
-        // Z is now Reference counted for
-        let z = Rc::new(RefCell::new(z));
+        Ok(self.insert_at(y, as_left, key))
+    }
+
+    /// Links a new node holding `key` below `y` and restores the red-black
+    /// properties. `y` must be T.nil for an empty tree, otherwise the child of
+    /// `y` on the requested side must be T.nil and `key` must belong there.
+    fn insert_at(
+        &mut self,
+        y: Rc<RefCell<Node<T>>>,
+        as_left: bool,
+        key: T,
+    ) -> Rc<RefCell<Node<T>>> {
+        self.push_path(&y);
+        let z = self.new_node(key);
+        z.borrow_mut().set_parent(y.clone());
 
         if y.borrow().is_nil() {
             self.root = z.clone();
-        } else if z.borrow().key < y.borrow().key {
+        } else if as_left {
             y.borrow_mut().set_left_child(z.clone());
         } else {
             y.borrow_mut().set_right_child(z.clone());
@@ -55,177 +161,134 @@ impl<T: PartialOrd + Clone + PartialEq + Debug + Default> Tree<T> {
         z.borrow_mut().set_left_child(self.sentinel.clone());
         z.borrow_mut().set_right_child(self.sentinel.clone());
         z.borrow_mut().color = NodeColor::Red;
-        self.insert_fix_up(z);
+        let mut ancestor = z.clone();
+        while !ancestor.borrow().is_nil() {
+            self.update(&ancestor);
+            let parent = ancestor.borrow().parent().clone();
+            ancestor = parent;
+        }
+        self.notify(|o| o.on_insert(&z.borrow().key));
+        self.insert_fix_up(z.clone());
         self.length += 1;
+        z
     }
 
     fn insert_fix_up(&mut self, mut z: Rc<RefCell<Node<T>>>) {
         while z.borrow().parent().borrow().color == NodeColor::Red {
-            if z.borrow().parent() == z.borrow().parent().borrow().parent().borrow().left() {
-                let y = z
-                    .borrow()
-                    .parent()
-                    .borrow()
-                    .parent()
-                    .borrow()
-                    .right()
-                    .clone();
+            let parent = z.borrow().parent().clone();
+            let grandparent = parent.borrow().parent().clone();
+            if Rc::ptr_eq(&parent, grandparent.borrow().left()) {
+                let y = grandparent.borrow().right().clone();
                 // Case 1
                 if y.borrow().color == NodeColor::Red {
-                    z.borrow_mut().parent_mut().borrow_mut().color = NodeColor::Black;
-                    y.borrow_mut().color = NodeColor::Black;
-                    z.borrow_mut()
-                        .parent_mut()
-                        .borrow_mut()
-                        .parent_mut()
-                        .borrow_mut()
-                        .color = NodeColor::Red;
-                    let z_tmp = z.borrow().parent().borrow().parent().clone();
-                    z = z_tmp;
+                    self.notify(|o| o.on_insert_case(1));
+                    self.recolor(&parent, NodeColor::Black);
+                    self.recolor(&y, NodeColor::Black);
+                    self.recolor(&grandparent, NodeColor::Red);
+                    z = grandparent;
                 } else {
                     // Case 2
-                    if &z == z.borrow().parent().borrow().right() {
-                        let z_tmp = z.borrow().parent().clone();
-                        z = z_tmp;
+                    if Rc::ptr_eq(&z, parent.borrow().right()) {
+                        self.notify(|o| o.on_insert_case(2));
+                        z = parent;
                         self.left_rotate(z.clone());
                     }
                     // Case 3
-                    z.borrow_mut().parent_mut().borrow_mut().color = NodeColor::Black;
-                    z.borrow_mut()
-                        .parent_mut()
-                        .borrow_mut()
-                        .parent_mut()
-                        .borrow_mut()
-                        .color = NodeColor::Red;
-                    let x = z.borrow().parent().borrow().parent().clone();
-                    self.right_rotate(x);
+                    self.notify(|o| o.on_insert_case(3));
+                    let parent = z.borrow().parent().clone();
+                    self.recolor(&parent, NodeColor::Black);
+                    self.recolor(&grandparent, NodeColor::Red);
+                    self.right_rotate(grandparent);
                 }
             } else {
-                let y = z
-                    .borrow()
-                    .parent()
-                    .borrow()
-                    .parent()
-                    .borrow()
-                    .left()
-                    .clone();
+                let y = grandparent.borrow().left().clone();
                 // Case 4
                 if y.borrow().color == NodeColor::Red {
-                    z.borrow_mut().parent_mut().borrow_mut().color = NodeColor::Black;
-                    y.borrow_mut().color = NodeColor::Black;
-                    z.borrow_mut()
-                        .parent_mut()
-                        .borrow_mut()
-                        .parent_mut()
-                        .borrow_mut()
-                        .color = NodeColor::Red;
-                    let z_tmp = z.borrow().parent().borrow().parent().clone();
-                    z = z_tmp;
+                    self.notify(|o| o.on_insert_case(4));
+                    self.recolor(&parent, NodeColor::Black);
+                    self.recolor(&y, NodeColor::Black);
+                    self.recolor(&grandparent, NodeColor::Red);
+                    z = grandparent;
                 } else {
                     // Case 5
-                    if &z == z.borrow().parent().borrow().left() {
-                        let z_tmp = z.borrow().parent().clone();
-                        z = z_tmp;
+                    if Rc::ptr_eq(&z, parent.borrow().left()) {
+                        self.notify(|o| o.on_insert_case(5));
+                        z = parent;
                         self.right_rotate(z.clone());
                     }
                     // Case 6
-                    z.borrow_mut().parent_mut().borrow_mut().color = NodeColor::Black;
-                    z.borrow_mut()
-                        .parent_mut()
-                        .borrow_mut()
-                        .parent_mut()
-                        .borrow_mut()
-                        .color = NodeColor::Red;
-                    let x = z.borrow().parent().borrow().parent().clone();
-                    self.left_rotate(x);
+                    self.notify(|o| o.on_insert_case(6));
+                    let parent = z.borrow().parent().clone();
+                    self.recolor(&parent, NodeColor::Black);
+                    self.recolor(&grandparent, NodeColor::Red);
+                    self.left_rotate(grandparent);
                 }
             }
         }
-        self.root.borrow_mut().color = NodeColor::Black;
+        let root = self.root.clone();
+        self.recolor(&root, NodeColor::Black);
     }
 
-    fn left_rotate(&mut self, x: Rc<RefCell<Node<T>>>) {
-        // Assumes that x.right != T.nil
-        if !x.borrow().right().borrow().is_nil() {
-            // let y = x.borrow_mut().right.take().unwrap();
-            let y = x.borrow().right().clone();
-            x.borrow_mut().set_right_child(y.borrow().left().clone());
-            if !y.borrow().left().borrow().is_nil() {
-                y.borrow_mut().left_mut().borrow_mut().set_parent(x.clone());
-            }
-            y.borrow_mut().set_parent(x.borrow().parent().clone());
-            if x.borrow().parent().borrow().is_nil() {
-                self.root = y.clone();
-            } else if &x == x.borrow().parent().borrow().left() {
-                x.borrow_mut()
-                    .parent_mut()
-                    .borrow_mut()
-                    .set_left_child(y.clone());
-            } else {
-                x.borrow_mut()
-                    .parent_mut()
-                    .borrow_mut()
-                    .set_right_child(y.clone());
-            }
+    /// Sets the color of `node`, notifying the observer if it changed.
+    fn recolor(&mut self, node: &Rc<RefCell<Node<T>>>, color: NodeColor) {
+        if node.borrow().color != color {
+            node.borrow_mut().color = color;
+            self.notify(|o| {
+                let node = node.borrow();
+                o.on_recolor(&node.key, &node.color)
+            });
+        }
+    }
 
-            y.borrow_mut().set_left_child(x.clone());
-            x.borrow_mut().set_parent(y);
-        } else {
-            panic!(
-                "Invariant violated. The right child of {:?} must not be T.nil.",
-                x
-            );
+    /// Passes a notification to the observer, followed by a snapshot if it
+    /// asked for them.
+    fn notify(&mut self, notification: impl FnOnce(&mut O)) {
+        notification(&mut self.observer);
+        if O::SNAPSHOTS {
+            let shape = self.to_shape();
+            self.observer.on_snapshot(shape);
         }
     }
 
+    fn left_rotate(&mut self, x: Rc<RefCell<Node<T>>>) {
+        self.push_down(&x);
+        self.push_down(x.borrow().right());
+        bst::left_rotate(&mut self.root, x.clone());
+        self.update(&x);
+        self.update(x.borrow().parent());
+        self.notify(|o| o.on_left_rotate(&x.borrow().key));
+    }
+
     fn right_rotate(&mut self, y: Rc<RefCell<Node<T>>>) {
-        // Assumes that x.left != T.nil
-        if !y.borrow().left().borrow().is_nil() {
-            // let x = y.clone().borrow_mut().left.take().unwrap();
-            let x = y.borrow().left().clone();
-            y.borrow_mut().set_left_child(x.borrow().right().clone());
-            if !x.borrow().right().borrow().is_nil() {
-                x.borrow_mut()
-                    .right_mut()
-                    .borrow_mut()
-                    .set_parent(y.clone());
-            }
-            x.borrow_mut().set_parent(y.borrow().parent().clone());
-            if y.borrow().parent().borrow().is_nil() {
-                self.root = x.clone();
-            } else if &y.clone() == y.borrow().parent().borrow().right() {
-                y.borrow_mut()
-                    .parent_mut()
-                    .borrow_mut()
-                    .set_right_child(x.clone());
-            } else {
-                y.borrow_mut()
-                    .parent_mut()
-                    .borrow_mut()
-                    .set_left_child(x.clone());
-            }
-            x.borrow_mut().set_right_child(y.clone());
-            y.borrow_mut().set_parent(x);
-        } else {
-            panic!(
-                "Invariant violated. The left child of {:?} must not be T.nil.",
-                y
-            );
-        }
+        self.push_down(&y);
+        self.push_down(y.borrow().left());
+        bst::right_rotate(&mut self.root, y.clone());
+        self.update(&y);
+        self.update(y.borrow().parent());
+        self.notify(|o| o.on_right_rotate(&y.borrow().key));
     }
 
     pub fn delete(&mut self, key: T) {
-        let node = self.search(key);
-        if node.is_some() {
-            self.delete_node(node.as_ref().unwrap().clone())
+        if let Some(node) = bst::search(&self.root, &key) {
+            self.delete_node(node);
+            self.length -= 1;
+            self.check_invariants("delete", Some(&key));
         }
-        self.length -= 1;
     }
 
     fn delete_node(&mut self, z: Rc<RefCell<Node<T>>>) {
+        // Nodes up to the successor of z may move, see below.
+        let right = z.borrow().right().clone();
+        if z.borrow().left().borrow().is_nil() || right.borrow().is_nil() {
+            self.push_path(&z);
+        } else {
+            self.push_path(&bst::minimum_node(right));
+        }
         let mut y = z.clone();
         let mut y_color = y.borrow().color.clone();
         let x;
+        // The lowest node whose subtree loses a node.
+        let mut shrunk = z.borrow().parent().clone();
         if z.borrow().left().borrow().is_nil() {
             x = z.borrow().right().clone();
             let u = z.clone();
@@ -242,7 +305,8 @@ impl<T: PartialOrd + Clone + PartialEq + Debug + Default> Tree<T> {
                 .expect("Expected this to be set");
             y_color = y.borrow().color.clone();
             x = y.borrow().right().clone();
-            if &y != z.borrow().right() {
+            if !Rc::ptr_eq(&y, z.borrow().right()) {
+                shrunk = y.borrow().parent().clone();
                 let u = y.clone();
                 let v = y.borrow().right().clone();
                 self.transplant(u, v);
@@ -252,6 +316,7 @@ impl<T: PartialOrd + Clone + PartialEq + Debug + Default> Tree<T> {
                     .borrow_mut()
                     .set_parent(y.clone());
             } else {
+                shrunk = y.clone();
                 x.borrow_mut().set_parent(y.clone());
             }
             let u = z.clone();
@@ -262,95 +327,108 @@ impl<T: PartialOrd + Clone + PartialEq + Debug + Default> Tree<T> {
             y.borrow_mut().color = z.borrow().color.clone();
         }
 
+        while !shrunk.borrow().is_nil() {
+            self.update(&shrunk);
+            let parent = shrunk.borrow().parent().clone();
+            shrunk = parent;
+        }
+
+        z.borrow_mut().generation = 0;
+        self.notify(|o| o.on_delete(&z.borrow().key));
         if y_color == NodeColor::Black {
-            self.delete_fix_up(x);
+            self.delete_fix_up(x.clone());
+        }
+        // The fix-up reads the parent of x even if x is T.nil. Drop that link
+        // again, so that T.nil never keeps a node alive.
+        if x.borrow().is_nil() {
+            x.borrow_mut().parent = None;
         }
     }
 
     fn transplant(&mut self, u: Rc<RefCell<Node<T>>>, v: Rc<RefCell<Node<T>>>) {
-        if u.borrow().parent().borrow().is_nil() {
-            self.root = v.clone();
-        } else if &u == u.borrow().parent().borrow().left() {
-            u.borrow_mut()
-                .parent_mut()
-                .borrow_mut()
-                .set_left_child(v.clone());
-        } else {
-            u.borrow_mut()
-                .parent_mut()
-                .borrow_mut()
-                .set_right_child(v.clone());
-        }
-        v.borrow_mut().set_parent(u.borrow().parent().clone());
+        bst::transplant(&mut self.root, u, v);
     }
 
     fn delete_fix_up(&mut self, mut x: Rc<RefCell<Node<T>>>) {
-        while x != self.root && x.borrow().color == NodeColor::Black {
-            if &x == x.borrow().parent().borrow().left() {
-                let mut w = x.borrow().parent().borrow().right().clone();
+        while !Rc::ptr_eq(&x, &self.root) && x.borrow().color == NodeColor::Black {
+            let parent = x.borrow().parent().clone();
+            if Rc::ptr_eq(&x, parent.borrow().left()) {
+                let mut w = parent.borrow().right().clone();
                 // Case 1
                 if w.borrow().color == NodeColor::Red {
-                    w.borrow_mut().color = NodeColor::Black;
-                    x.borrow_mut().parent_mut().borrow_mut().color = NodeColor::Red;
-                    self.left_rotate(x.borrow().parent().clone());
-                    w = x.borrow().parent().borrow().right().clone();
+                    self.notify(|o| o.on_delete_case(1));
+                    self.recolor(&w, NodeColor::Black);
+                    self.recolor(&parent, NodeColor::Red);
+                    self.left_rotate(parent.clone());
+                    w = parent.borrow().right().clone();
                 }
-
+                // Case 2
                 if w.borrow().left().borrow().color == NodeColor::Black
                     && w.borrow().right().borrow().color == NodeColor::Black
                 {
-                    w.borrow_mut().color = NodeColor::Red;
-                    let x_tmp = x.borrow().parent().clone();
-                    x = x_tmp;
+                    self.notify(|o| o.on_delete_case(2));
+                    self.recolor(&w, NodeColor::Red);
+                    x = parent;
                 } else {
                     // Case 3
-                    if w.borrow_mut().right().borrow().color == NodeColor::Black {
-                        w.borrow_mut().left().borrow_mut().color = NodeColor::Black;
-                        w.borrow_mut().color = NodeColor::Red;
+                    if w.borrow().right().borrow().color == NodeColor::Black {
+                        self.notify(|o| o.on_delete_case(3));
+                        let w_left = w.borrow().left().clone();
+                        self.recolor(&w_left, NodeColor::Black);
+                        self.recolor(&w, NodeColor::Red);
                         self.right_rotate(w.clone());
-                        w = x.borrow().parent().borrow().right().clone();
+                        w = parent.borrow().right().clone();
                     }
                     // Case 4
-                    w.borrow_mut().color = x.borrow().parent().borrow().color.clone();
-                    x.borrow_mut().parent_mut().borrow_mut().color = NodeColor::Black;
-                    w.borrow_mut().right_mut().borrow_mut().color = NodeColor::Black;
-                    self.left_rotate(x.borrow().parent().clone());
+                    self.notify(|o| o.on_delete_case(4));
+                    let parent_color = parent.borrow().color.clone();
+                    self.recolor(&w, parent_color);
+                    self.recolor(&parent, NodeColor::Black);
+                    let w_right = w.borrow().right().clone();
+                    self.recolor(&w_right, NodeColor::Black);
+                    self.left_rotate(parent);
                     x = self.root.clone();
                 }
             } else {
-                let mut w = x.borrow().parent().borrow().left().clone();
+                let mut w = parent.borrow().left().clone();
                 // Case 5
-                if w.borrow_mut().color == NodeColor::Red {
-                    w.borrow_mut().color = NodeColor::Black;
-                    x.borrow_mut().parent_mut().borrow_mut().color = NodeColor::Red;
-                    self.right_rotate(x.borrow().parent().clone());
-                    w = x.borrow().parent().borrow().left().clone();
+                if w.borrow().color == NodeColor::Red {
+                    self.notify(|o| o.on_delete_case(5));
+                    self.recolor(&w, NodeColor::Black);
+                    self.recolor(&parent, NodeColor::Red);
+                    self.right_rotate(parent.clone());
+                    w = parent.borrow().left().clone();
                 }
                 // Case 6
                 if w.borrow().right().borrow().color == NodeColor::Black
                     && w.borrow().left().borrow().color == NodeColor::Black
                 {
-                    w.borrow_mut().color = NodeColor::Red;
-                    let x_tmp = x.borrow().parent().clone();
-                    x = x_tmp;
+                    self.notify(|o| o.on_delete_case(6));
+                    self.recolor(&w, NodeColor::Red);
+                    x = parent;
                 } else {
                     // Case 7
                     if w.borrow().left().borrow().color == NodeColor::Black {
-                        w.borrow_mut().right_mut().borrow_mut().color = NodeColor::Black;
-                        w.borrow_mut().color = NodeColor::Red;
+                        self.notify(|o| o.on_delete_case(7));
+                        let w_right = w.borrow().right().clone();
+                        self.recolor(&w_right, NodeColor::Black);
+                        self.recolor(&w, NodeColor::Red);
                         self.left_rotate(w.clone());
-                        w = x.borrow_mut().parent().borrow().left().clone();
+                        w = parent.borrow().left().clone();
                     }
                     // Case 8
-                    w.borrow_mut().color = x.borrow().parent().borrow().color.clone();
-                    x.borrow_mut().parent_mut().borrow_mut().color = NodeColor::Black;
-                    w.borrow_mut().left_mut().borrow_mut().color = NodeColor::Black;
-                    self.right_rotate(x.borrow().parent().clone());
+                    self.notify(|o| o.on_delete_case(8));
+                    let parent_color = parent.borrow().color.clone();
+                    self.recolor(&w, parent_color);
+                    self.recolor(&parent, NodeColor::Black);
+                    let w_left = w.borrow().left().clone();
+                    self.recolor(&w_left, NodeColor::Black);
+                    self.right_rotate(parent);
                     x = self.root.clone();
                 }
             }
         }
-        x.borrow_mut().color = NodeColor::Black;
+        self.recolor(&x, NodeColor::Black);
     }
 
     pub fn contains_key(&self, key: T) -> bool {
@@ -358,20 +436,7 @@ impl<T: PartialOrd + Clone + PartialEq + Debug + Default> Tree<T> {
     }
 
     fn search(&self, key: T) -> Option<Rc<RefCell<Node<T>>>> {
-        let mut node = self.root.clone();
-        while !node.borrow().is_nil() {
-            let node_key = node.borrow().key.clone();
-            if node_key == key {
-                return Some(node);
-            } else if key < node_key {
-                let node_tmp = node.borrow().left().clone();
-                node = node_tmp;
-            } else {
-                let node_tmp = node.borrow().right().clone();
-                node = node_tmp;
-            }
-        }
-        None
+        bst::search(&self.root, &key)
     }
 
     pub fn is_empty(&self) -> bool {
@@ -383,29 +448,71 @@ impl<T: PartialOrd + Clone + PartialEq + Debug + Default> Tree<T> {
     }
 
     pub fn clear(&mut self) {
-        self.root = self.sentinel.clone();
+        let root = core::mem::replace(&mut self.root, self.sentinel.clone());
+        bst::unlink(root);
+        self.sentinel.borrow_mut().parent = None;
         self.length = 0;
+        self.check_invariants("clear", None);
     }
 
-    pub fn minimum(&self) -> Option<T> {
+    /// Returns a reference to the smallest key, or `None` if the tree is empty.
+    pub fn first(&self) -> Option<&T> {
         if self.root.borrow().is_nil() {
             return None;
         }
 
         self.minimum_node(self.root.clone())
-            .and_then(|node| Some(node.borrow().key.clone()))
+            .map(|node| self.key_ref(&node))
     }
-    fn minimum_node(&self, node: Rc<RefCell<Node<T>>>) -> Option<Rc<RefCell<Node<T>>>> {
-        if node.borrow().left().borrow().is_nil() {
-            return Some(node);
+
+    /// Returns a reference to the largest key, or `None` if the tree is empty.
+    pub fn last(&self) -> Option<&T> {
+        if self.root.borrow().is_nil() {
+            return None;
         }
 
-        let mut x = node.clone();
-        while !x.borrow().left().borrow().is_nil() {
-            let x_tmp = x.borrow().left().clone();
-            x = x_tmp;
+        self.maximum_node(self.root.clone())
+            .map(|node| self.key_ref(&node))
+    }
+
+    /// Removes and returns the smallest key, or `None` if the tree is empty.
+    pub fn pop_first(&mut self) -> Option<T> {
+        if self.root.borrow().is_nil() {
+            return None;
         }
-        Some(x)
+
+        let node = self.minimum_node(self.root.clone())?;
+        self.delete_node(node.clone());
+        self.length -= 1;
+        let key = node.borrow().key.clone();
+        self.check_invariants("pop_first", Some(&key));
+        Some(key)
+    }
+
+    /// Removes and returns the largest key, or `None` if the tree is empty.
+    pub fn pop_last(&mut self) -> Option<T> {
+        if self.root.borrow().is_nil() {
+            return None;
+        }
+
+        let node = self.maximum_node(self.root.clone())?;
+        self.delete_node(node.clone());
+        self.length -= 1;
+        let key = node.borrow().key.clone();
+        self.check_invariants("pop_last", Some(&key));
+        Some(key)
+    }
+
+    pub fn minimum(&self) -> Option<T> {
+        if self.root.borrow().is_nil() {
+            return None;
+        }
+
+        self.minimum_node(self.root.clone())
+            .map(|node| node.borrow().key.clone())
+    }
+    fn minimum_node(&self, node: Rc<RefCell<Node<T>>>) -> Option<Rc<RefCell<Node<T>>>> {
+        Some(bst::minimum_node(node))
     }
 
     pub fn maximum(&self) -> Option<T> {
@@ -414,25 +521,128 @@ impl<T: PartialOrd + Clone + PartialEq + Debug + Default> Tree<T> {
         }
 
         self.maximum_node(self.root.clone())
-            .and_then(|node| Some(node.borrow().key.clone()))
+            .map(|node| node.borrow().key.clone())
     }
     fn maximum_node(&self, node: Rc<RefCell<Node<T>>>) -> Option<Rc<RefCell<Node<T>>>> {
-        if node.borrow().right().borrow().is_nil() {
-            return Some(node);
+        Some(bst::maximum_node(node))
+    }
+
+    /// Returns the node following `x` in sorted order by climbing the parent
+    /// links, or `None` if `x` holds the largest key.
+    fn successor_node(&self, x: Rc<RefCell<Node<T>>>) -> Option<Rc<RefCell<Node<T>>>> {
+        if !x.borrow().right().borrow().is_nil() {
+            return self.minimum_node(x.borrow().right().clone());
+        }
+
+        let mut x = x;
+        let mut y = x.borrow().parent().clone();
+        while !y.borrow().is_nil() && Rc::ptr_eq(&x, y.borrow().right()) {
+            x = y.clone();
+            let y_tmp = y.borrow().parent().clone();
+            y = y_tmp;
+        }
+        if y.borrow().is_nil() {
+            None
+        } else {
+            Some(y)
+        }
+    }
+
+    /// Returns the node preceding `x` in sorted order by climbing the parent
+    /// links, or `None` if `x` holds the smallest key.
+    fn predecessor_node(&self, x: Rc<RefCell<Node<T>>>) -> Option<Rc<RefCell<Node<T>>>> {
+        if !x.borrow().left().borrow().is_nil() {
+            return self.maximum_node(x.borrow().left().clone());
+        }
+
+        let mut x = x;
+        let mut y = x.borrow().parent().clone();
+        while !y.borrow().is_nil() && Rc::ptr_eq(&x, y.borrow().left()) {
+            x = y.clone();
+            let y_tmp = y.borrow().parent().clone();
+            y = y_tmp;
+        }
+        if y.borrow().is_nil() {
+            None
+        } else {
+            Some(y)
         }
+    }
+
+    /// Returns the node holding the smallest key that is greater than `key`.
+    fn upper_bound_node(&self, key: &T) -> Option<Rc<RefCell<Node<T>>>> {
+        let mut node = self.root.clone();
+        let mut candidate = None;
+        while !node.borrow().is_nil() {
+            let node_tmp = if *key < node.borrow().key {
+                candidate = Some(node.clone());
+                node.borrow().left().clone()
+            } else {
+                node.borrow().right().clone()
+            };
+            node = node_tmp;
+        }
+        candidate
+    }
+
+    /// Returns the node holding the smallest key that is not less than `key`.
+    fn lower_bound_node(&self, key: &T) -> Option<Rc<RefCell<Node<T>>>> {
+        let mut node = self.root.clone();
+        let mut candidate = None;
+        while !node.borrow().is_nil() {
+            let node_tmp = if node.borrow().key < *key {
+                node.borrow().right().clone()
+            } else {
+                candidate = Some(node.clone());
+                node.borrow().left().clone()
+            };
+            node = node_tmp;
+        }
+        candidate
+    }
+
+    /// Returns the key of `node` borrowed for as long as the tree is, without
+    /// holding a `Ref` guard. `node` must belong to this tree.
+    ///
+    /// This relies on a rule every method of the tree and of its wrappers
+    /// keeps: only methods taking `&mut self` call `borrow_mut` on a node,
+    /// and no `Ref` or `RefMut` to a node outlives the call that took it.
+    pub(crate) fn key_ref<'a>(&'a self, node: &Rc<RefCell<Node<T>>>) -> &'a T {
+        debug_assert!(node.try_borrow().is_ok(), "Node is mutably borrowed");
+        // SAFETY: the tree holds a strong reference to `node`, and removing
+        // the node takes `&mut self`, so it lives for `'a`. By the rule above
+        // no `RefMut` to it exists now, and none can be taken while `'a`
+        // keeps the tree shared.
+        unsafe { &(*node.as_ptr()).key }
+    }
+}
+
+impl<T: PartialOrd + Clone + PartialEq + Debug + Default, O: Observer<T> + Default> Default
+    for Tree<T, O>
+{
+    fn default() -> Self {
+        Self::with_observer(O::default())
+    }
+}
 
-        let mut x = node.clone();
-        while !x.borrow().right().borrow().is_nil() {
-            let x_tmp = x.borrow().right().clone();
-            x = x_tmp;
+/// Collects the keys into a tree, keeping the first of any duplicates like
+/// [`Tree::insert`].
+impl<T: PartialOrd + Clone + PartialEq + Debug + Default, O: Observer<T> + Default> FromIterator<T>
+    for Tree<T, O>
+{
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut tree = Tree::default();
+        for key in iter {
+            tree.insert(key);
         }
-        Some(x)
+        tree
     }
 }
 
-impl<T: Debug> Debug for Tree<T> {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{:?}", self.root)
+impl<T, O> Drop for Tree<T, O> {
+    fn drop(&mut self) {
+        bst::unlink(self.root.clone());
+        self.sentinel.borrow_mut().parent = None;
     }
 }
 
@@ -486,7 +696,7 @@ fn tree_equality_dfs<T: PartialEq>(
     true
 }
 
-impl<T: PartialEq> PartialEq<Self> for Tree<T> {
+impl<T: PartialEq, O> PartialEq<Self> for Tree<T, O> {
     fn eq(&self, other: &Self) -> bool {
         tree_equality_dfs(Some(self.root.clone()), Some(other.root.clone()))
     }
@@ -0,0 +1,132 @@
+use crate::{
+    node::Node,
+    tree::{Observer, Tree},
+};
+use alloc::{rc::Rc, vec::Vec};
+use core::{cell::RefCell, fmt::Debug};
+
+/// Keeps a summary of every subtree in the key at its root, for the wrappers
+/// around [`Tree`] elsewhere in the crate that store more than the ordered
+/// key in `T`.
+///
+/// The tree calls `update` wherever it maintains the subtree sizes: on every
+/// node whose children changed, children before parents.
+///
+/// A key may also hold a change that is already applied to it but still owed
+/// to the keys below it. The tree calls `push_down` to pass such a change on
+/// to the children before it moves a node away from its ancestors: on both
+/// nodes of a rotation and on the path to every node that is linked or
+/// unlinked.
+pub(crate) struct Augmentation<T> {
+    /// Recomputes the summary in a key from the keys of its children, `None`
+    /// standing for T.nil.
+    pub(crate) update: fn(&mut T, Option<&T>, Option<&T>),
+    /// Passes the change pending in a key on to the keys of its children,
+    /// each given with the size of its subtree.
+    pub(crate) push_down: Option<fn(&mut T, Children<'_, T>)>,
+}
+
+/// The keys of the left and right child and the sizes of their subtrees,
+/// `None` standing for T.nil.
+pub(crate) type Children<'a, T> = [Option<(&'a mut T, usize)>; 2];
+
+impl<T> Clone for Augmentation<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Augmentation<T> {}
+
+impl<T: PartialOrd + Clone + PartialEq + Debug + Default> Tree<T> {
+    /// Creates an empty tree that keeps its subtree summaries up to date with
+    /// `augmentation`.
+    pub(crate) fn with_augmentation(augmentation: Augmentation<T>) -> Tree<T> {
+        let mut tree = Tree::new();
+        tree.augmentation = Some(augmentation);
+        tree
+    }
+}
+
+impl<T: PartialOrd + Clone + PartialEq + Debug + Default, O: Observer<T>> Tree<T, O> {
+    /// Recomputes the subtree size of `node` from its children, and its
+    /// summary if the tree is augmented. Does nothing for T.nil.
+    pub(crate) fn update(&mut self, node: &Rc<RefCell<Node<T>>>) {
+        if node.borrow().is_nil() {
+            return;
+        }
+        let mut node = node.borrow_mut();
+        let node = &mut *node;
+        let left = node.left.as_ref().unwrap().borrow();
+        let right = node.right.as_ref().unwrap().borrow();
+        node.size = left.size + right.size + 1;
+        if let Some(augmentation) = &self.augmentation {
+            let left = (!left.is_nil()).then_some(&left.key);
+            let right = (!right.is_nil()).then_some(&right.key);
+            (augmentation.update)(&mut node.key, left, right);
+        }
+    }
+
+    /// Passes the change pending in `node` on to its children. Does nothing
+    /// for T.nil or unless the tree holds pending changes.
+    pub(crate) fn push_down(&mut self, node: &Rc<RefCell<Node<T>>>) {
+        let Some(push_down) = self.augmentation.and_then(|a| a.push_down) else {
+            return;
+        };
+        if node.borrow().is_nil() {
+            return;
+        }
+        let mut node = node.borrow_mut();
+        let node = &mut *node;
+        // Both children may be T.nil, which must not be borrowed twice.
+        let mut children =
+            [node.left.as_ref().unwrap(), node.right.as_ref().unwrap()].map(|child| {
+                let is_nil = child.borrow().is_nil();
+                (!is_nil).then(|| child.borrow_mut())
+            });
+        let [left, right] = &mut children;
+        push_down(
+            &mut node.key,
+            [left, right].map(|child| child.as_deref_mut().map(|c| (&mut c.key, c.size))),
+        );
+    }
+
+    /// Passes the pending changes on the path from the root down to `node`
+    /// on, so that no ancestor of `node` owes a change to it. `node` itself
+    /// is pushed down as well.
+    pub(crate) fn push_path(&mut self, node: &Rc<RefCell<Node<T>>>) {
+        if self.augmentation.and_then(|a| a.push_down).is_none() {
+            return;
+        }
+        let mut path = Vec::new();
+        let mut node = node.clone();
+        while !node.borrow().is_nil() {
+            let parent = node.borrow().parent().clone();
+            path.push(node);
+            node = parent;
+        }
+        for node in path.iter().rev() {
+            self.push_down(node);
+        }
+    }
+
+    /// Returns the root, which is T.nil if the tree is empty, for wrappers
+    /// that fold over the subtree summaries.
+    pub(crate) fn root_node(&self) -> &Rc<RefCell<Node<T>>> {
+        &self.root
+    }
+
+    /// Updates `node` and its ancestors up to the root, after the key of
+    /// `node` changed. Does nothing unless the tree is augmented.
+    pub(crate) fn update_path(&mut self, node: &Rc<RefCell<Node<T>>>) {
+        if self.augmentation.is_none() {
+            return;
+        }
+        let mut node = node.clone();
+        while !node.borrow().is_nil() {
+            self.update(&node);
+            let parent = node.borrow().parent().clone();
+            node = parent;
+        }
+    }
+}
@@ -0,0 +1,252 @@
+use crate::{
+    node::Node,
+    tree::{NoopObserver, Observer, Tree},
+};
+use alloc::rc::Rc;
+use core::{
+    cell::RefCell,
+    fmt::{self, Debug, Display, Formatter},
+};
+
+#[cfg(test)]
+mod cursor_tests;
+
+/// A read-only position in a [`Tree`].
+///
+/// A cursor either points at an element or at the "ghost" position past the
+/// end of the tree, which sits between the largest and the smallest element.
+/// Moving is done through the parent links of the nodes, so stepping to a
+/// neighbour costs amortized `O(1)` instead of a new search from the root.
+pub struct Cursor<'a, T, O = NoopObserver> {
+    tree: &'a Tree<T, O>,
+    current: Rc<RefCell<Node<T>>>,
+}
+
+/// A position in a [`Tree`] that can also insert and remove elements.
+///
+/// See [`Cursor`] for how positions are defined.
+pub struct CursorMut<'a, T, O = NoopObserver> {
+    tree: &'a mut Tree<T, O>,
+    current: Rc<RefCell<Node<T>>>,
+}
+
+/// Returned by [`CursorMut::insert_before`] and [`CursorMut::insert_after`]
+/// when the key does not belong between the neighbouring elements, including
+/// when it equals one of them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnorderedKeyError;
+
+impl Display for UnorderedKeyError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "key is not properly ordered relative to neighbors")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for UnorderedKeyError {}
+
+impl<T: PartialOrd + Clone + PartialEq + Debug + Default, O: Observer<T>> Tree<T, O> {
+    /// Returns a cursor pointing at the smallest element that is not less
+    /// than `key`, or at the ghost position if there is none.
+    pub fn lower_bound_cursor(&self, key: &T) -> Cursor<'_, T, O> {
+        let current = self
+            .lower_bound_node(key)
+            .unwrap_or_else(|| self.sentinel.clone());
+        Cursor {
+            tree: self,
+            current,
+        }
+    }
+
+    /// Returns a mutable cursor pointing at the smallest element that is not
+    /// less than `key`, or at the ghost position if there is none.
+    pub fn lower_bound_cursor_mut(&mut self, key: &T) -> CursorMut<'_, T, O> {
+        let current = self
+            .lower_bound_node(key)
+            .unwrap_or_else(|| self.sentinel.clone());
+        CursorMut {
+            tree: self,
+            current,
+        }
+    }
+
+    /// The node after `current`, wrapping through the ghost position.
+    fn next_position(&self, current: &Rc<RefCell<Node<T>>>) -> Rc<RefCell<Node<T>>> {
+        let next = if current.borrow().is_nil() {
+            if self.root.borrow().is_nil() {
+                None
+            } else {
+                self.minimum_node(self.root.clone())
+            }
+        } else {
+            self.successor_node(current.clone())
+        };
+        next.unwrap_or_else(|| self.sentinel.clone())
+    }
+
+    /// The node before `current`, wrapping through the ghost position.
+    fn prev_position(&self, current: &Rc<RefCell<Node<T>>>) -> Rc<RefCell<Node<T>>> {
+        let prev = if current.borrow().is_nil() {
+            if self.root.borrow().is_nil() {
+                None
+            } else {
+                self.maximum_node(self.root.clone())
+            }
+        } else {
+            self.predecessor_node(current.clone())
+        };
+        prev.unwrap_or_else(|| self.sentinel.clone())
+    }
+
+    fn peek_position(&self, position: &Rc<RefCell<Node<T>>>) -> Option<&T> {
+        if position.borrow().is_nil() {
+            None
+        } else {
+            Some(self.key_ref(position))
+        }
+    }
+}
+
+impl<'a, T: PartialOrd + Clone + PartialEq + Debug + Default, O: Observer<T>> Cursor<'a, T, O> {
+    /// Returns the element the cursor points at, or `None` at the ghost
+    /// position.
+    pub fn peek(&self) -> Option<&'a T> {
+        self.tree.peek_position(&self.current)
+    }
+
+    /// Returns the element after the cursor without moving it.
+    pub fn peek_next(&self) -> Option<&'a T> {
+        self.tree
+            .peek_position(&self.tree.next_position(&self.current))
+    }
+
+    /// Returns the element before the cursor without moving it.
+    pub fn peek_prev(&self) -> Option<&'a T> {
+        self.tree
+            .peek_position(&self.tree.prev_position(&self.current))
+    }
+
+    /// Moves to the next element. Moving past the largest element lands on
+    /// the ghost position, and moving from there lands on the smallest.
+    pub fn move_next(&mut self) {
+        self.current = self.tree.next_position(&self.current);
+    }
+
+    /// Moves to the previous element. Moving past the smallest element lands
+    /// on the ghost position, and moving from there lands on the largest.
+    pub fn move_prev(&mut self) {
+        self.current = self.tree.prev_position(&self.current);
+    }
+}
+
+impl<'a, T: PartialOrd + Clone + PartialEq + Debug + Default, O: Observer<T>> CursorMut<'a, T, O> {
+    /// Returns the element the cursor points at, or `None` at the ghost
+    /// position.
+    pub fn peek(&self) -> Option<&T> {
+        self.tree.peek_position(&self.current)
+    }
+
+    /// Returns the element after the cursor without moving it.
+    pub fn peek_next(&self) -> Option<&T> {
+        self.tree
+            .peek_position(&self.tree.next_position(&self.current))
+    }
+
+    /// Returns the element before the cursor without moving it.
+    pub fn peek_prev(&self) -> Option<&T> {
+        self.tree
+            .peek_position(&self.tree.prev_position(&self.current))
+    }
+
+    /// See [`Cursor::move_next`].
+    pub fn move_next(&mut self) {
+        self.current = self.tree.next_position(&self.current);
+    }
+
+    /// See [`Cursor::move_prev`].
+    pub fn move_prev(&mut self) {
+        self.current = self.tree.prev_position(&self.current);
+    }
+
+    /// Returns a read-only cursor at the same position.
+    pub fn as_cursor(&self) -> Cursor<'_, T, O> {
+        Cursor {
+            tree: self.tree,
+            current: self.current.clone(),
+        }
+    }
+
+    /// Removes the element the cursor points at and moves the cursor to the
+    /// following element. Returns `None` and does nothing at the ghost
+    /// position.
+    pub fn remove_current(&mut self) -> Option<T> {
+        if self.current.borrow().is_nil() {
+            return None;
+        }
+
+        // The CLRS deletion relinks nodes instead of moving keys around, so
+        // the successor is still the right node once `current` is gone.
+        let next = self.tree.next_position(&self.current);
+        let removed = core::mem::replace(&mut self.current, next);
+        self.tree.delete_node(removed.clone());
+        self.tree.length -= 1;
+        let key = removed.borrow().key.clone();
+        self.tree.check_invariants("remove_current", Some(&key));
+        Some(key)
+    }
+
+    /// Inserts `key` right before the cursor without searching from the root.
+    /// The cursor keeps pointing at the same element. At the ghost position the
+    /// key becomes the new largest element.
+    ///
+    /// Fails if `key` is not strictly between the previous element and the
+    /// current one.
+    pub fn insert_before(&mut self, key: T) -> Result<(), UnorderedKeyError> {
+        let prev = self.tree.prev_position(&self.current);
+        if !prev.borrow().is_nil() && key <= prev.borrow().key {
+            return Err(UnorderedKeyError);
+        }
+        if !self.current.borrow().is_nil() && self.current.borrow().key <= key {
+            return Err(UnorderedKeyError);
+        }
+
+        // Either the left child of the current node is free, or the previous
+        // node is the maximum of that left subtree and its right child is free.
+        // At the ghost position the previous node is the maximum of the tree.
+        let z = if !self.current.borrow().is_nil() && self.current.borrow().left().borrow().is_nil()
+        {
+            self.tree.insert_at(self.current.clone(), true, key)
+        } else {
+            self.tree.insert_at(prev, false, key)
+        };
+        self.tree
+            .check_invariants("insert_before", Some(&z.borrow().key));
+        Ok(())
+    }
+
+    /// Inserts `key` right after the cursor without searching from the root.
+    /// The cursor keeps pointing at the same element. At the ghost position the
+    /// key becomes the new smallest element.
+    ///
+    /// Fails if `key` is not strictly between the current element and the
+    /// next one.
+    pub fn insert_after(&mut self, key: T) -> Result<(), UnorderedKeyError> {
+        let next = self.tree.next_position(&self.current);
+        if !next.borrow().is_nil() && next.borrow().key <= key {
+            return Err(UnorderedKeyError);
+        }
+        if !self.current.borrow().is_nil() && key <= self.current.borrow().key {
+            return Err(UnorderedKeyError);
+        }
+
+        let z =
+            if !self.current.borrow().is_nil() && self.current.borrow().right().borrow().is_nil() {
+                self.tree.insert_at(self.current.clone(), false, key)
+            } else {
+                self.tree.insert_at(next, true, key)
+            };
+        self.tree
+            .check_invariants("insert_after", Some(&z.borrow().key));
+        Ok(())
+    }
+}
@@ -0,0 +1,157 @@
+use crate::tree::{Tree, UnorderedKeyError};
+use std::{vec, vec::Vec};
+
+fn collect_forward(tree: &Tree<i32>) -> Vec<i32> {
+    let mut cursor = tree.lower_bound_cursor(&i32::MIN);
+    let mut keys = vec![];
+    while let Some(key) = cursor.peek() {
+        keys.push(*key);
+        cursor.move_next();
+    }
+    keys
+}
+
+#[test]
+fn test_lower_bound_cursor() {
+    let tree: Tree<i32> = [10, 20, 30].into_iter().collect();
+
+    assert_eq!(tree.lower_bound_cursor(&5).peek(), Some(&10));
+    assert_eq!(tree.lower_bound_cursor(&10).peek(), Some(&10));
+    assert_eq!(tree.lower_bound_cursor(&11).peek(), Some(&20));
+    assert_eq!(tree.lower_bound_cursor(&30).peek(), Some(&30));
+    assert_eq!(tree.lower_bound_cursor(&31).peek(), None);
+
+    let empty: Tree<i32> = Tree::new();
+    assert_eq!(empty.lower_bound_cursor(&0).peek(), None);
+}
+
+#[test]
+fn test_cursor_walks_in_both_directions() {
+    let keys: Vec<i32> = (0..50).map(|i| (i * 37) % 50).collect();
+    let tree: Tree<i32> = keys.iter().copied().collect();
+
+    let mut cursor = tree.lower_bound_cursor(&0);
+    for expected in 0..50 {
+        assert_eq!(cursor.peek(), Some(&expected));
+        cursor.move_next();
+    }
+    assert_eq!(cursor.peek(), None);
+
+    for expected in (0..50).rev() {
+        cursor.move_prev();
+        assert_eq!(cursor.peek(), Some(&expected));
+    }
+    cursor.move_prev();
+    assert_eq!(cursor.peek(), None);
+}
+
+#[test]
+fn test_cursor_ghost_position_wraps() {
+    let tree: Tree<i32> = [1, 2, 3].into_iter().collect();
+
+    let mut cursor = tree.lower_bound_cursor(&4);
+    assert_eq!(cursor.peek(), None);
+    assert_eq!(cursor.peek_next(), Some(&1));
+    assert_eq!(cursor.peek_prev(), Some(&3));
+
+    cursor.move_next();
+    assert_eq!(cursor.peek(), Some(&1));
+    assert_eq!(cursor.peek_prev(), None);
+}
+
+#[test]
+fn test_cursor_mut_remove_current() {
+    let mut tree: Tree<i32> = [1, 2, 3, 4, 5, 6, 7, 8].into_iter().collect();
+
+    let mut cursor = tree.lower_bound_cursor_mut(&3);
+    assert_eq!(cursor.remove_current(), Some(3));
+    assert_eq!(cursor.peek(), Some(&4));
+    assert_eq!(cursor.remove_current(), Some(4));
+    assert_eq!(cursor.peek(), Some(&5));
+
+    cursor.move_prev();
+    assert_eq!(cursor.peek(), Some(&2));
+
+    assert_eq!(tree.len(), 6);
+    assert_eq!(collect_forward(&tree), vec![1, 2, 5, 6, 7, 8]);
+}
+
+#[test]
+fn test_cursor_mut_remove_last_moves_to_ghost() {
+    let mut tree: Tree<i32> = [1, 2].into_iter().collect();
+
+    let mut cursor = tree.lower_bound_cursor_mut(&2);
+    assert_eq!(cursor.remove_current(), Some(2));
+    assert_eq!(cursor.peek(), None);
+    assert_eq!(cursor.remove_current(), None);
+
+    assert_eq!(tree.len(), 1);
+}
+
+#[test]
+fn test_cursor_mut_insert_around_current() {
+    let mut tree: Tree<i32> = [10, 20, 30, 40].into_iter().collect();
+
+    let mut cursor = tree.lower_bound_cursor_mut(&20);
+    cursor.insert_before(15).unwrap();
+    cursor.insert_after(25).unwrap();
+    cursor.insert_before(18).unwrap();
+    assert_eq!(cursor.peek(), Some(&20));
+    assert_eq!(cursor.peek_prev(), Some(&18));
+    assert_eq!(cursor.peek_next(), Some(&25));
+
+    assert_eq!(tree.len(), 7);
+    assert_eq!(collect_forward(&tree), vec![10, 15, 18, 20, 25, 30, 40]);
+}
+
+#[test]
+fn test_cursor_mut_insert_checks_order() {
+    let mut tree: Tree<i32> = [10, 20, 30].into_iter().collect();
+
+    let mut cursor = tree.lower_bound_cursor_mut(&20);
+    assert_eq!(cursor.insert_before(5), Err(UnorderedKeyError));
+    assert_eq!(cursor.insert_before(25), Err(UnorderedKeyError));
+    assert_eq!(cursor.insert_after(15), Err(UnorderedKeyError));
+    assert_eq!(cursor.insert_after(35), Err(UnorderedKeyError));
+    assert_eq!(cursor.insert_before(10), Err(UnorderedKeyError));
+    assert_eq!(cursor.insert_before(20), Err(UnorderedKeyError));
+    assert_eq!(cursor.insert_after(20), Err(UnorderedKeyError));
+    assert_eq!(cursor.insert_after(30), Err(UnorderedKeyError));
+
+    assert_eq!(tree.len(), 3);
+}
+
+#[test]
+fn test_cursor_mut_insert_at_ghost() {
+    let mut tree = Tree::new();
+
+    let mut cursor = tree.lower_bound_cursor_mut(&0);
+    cursor.insert_before(2).unwrap();
+    cursor.insert_before(3).unwrap();
+    cursor.insert_after(1).unwrap();
+    assert_eq!(cursor.insert_after(4), Err(UnorderedKeyError));
+    assert_eq!(cursor.peek(), None);
+
+    assert_eq!(collect_forward(&tree), vec![1, 2, 3]);
+}
+
+#[test]
+fn test_cursor_mut_keeps_properties() {
+    let mut tree = Tree::new();
+    let mut cursor = tree.lower_bound_cursor_mut(&0);
+    for key in 0..100 {
+        cursor.insert_before(key).unwrap();
+    }
+    let mut cursor = tree.lower_bound_cursor_mut(&0);
+    while cursor.peek().is_some() {
+        cursor.remove_current();
+        cursor.move_next();
+    }
+
+    assert_eq!(tree.len(), 50);
+    assert_eq!(
+        collect_forward(&tree),
+        (0..100).filter(|key| key % 2 == 1).collect::<Vec<_>>()
+    );
+    crate::tree::tree_tests::assert_red_black_tree_properties(&tree);
+}
@@ -0,0 +1,239 @@
+use crate::{
+    node::{Node, NodeColor},
+    tree::{Observer, Shape, Tree},
+};
+use alloc::{collections::BTreeMap, format, rc::Rc, string::String, vec, vec::Vec};
+use core::{
+    cell::RefCell,
+    fmt::{self, Debug, Write},
+};
+#[cfg(feature = "std")]
+use std::io;
+
+#[cfg(test)]
+mod dot_tests;
+
+/// Controls which parts of the tree are emitted by [`Tree::to_dot_with`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DotOptions {
+    /// Draw the sentinel `T.nil` leaves as small black boxes.
+    pub show_nil: bool,
+    /// Draw a dashed edge from every node to the node its parent link points
+    /// to, so that broken links show up. A link to nothing or to a node
+    /// outside the tree ends in a box labelled `?`, and one to T.nil anywhere
+    /// but at the root in a box labelled `NIL`. Shapes have no parent links,
+    /// so [`Shape::to_dot_with`] and the frames of a
+    /// [`Tracer`](crate::tree::Tracer) leave these edges out.
+    pub show_parent_edges: bool,
+}
+
+/// Where the parent link of a node points.
+pub(super) enum ParentLink {
+    /// The node with this pre-order index.
+    Node(usize),
+    /// T.nil, which is correct only for the root.
+    Nil,
+    /// Nothing, or a node outside the tree.
+    Missing,
+}
+
+impl<T: PartialOrd + Clone + PartialEq + Debug + Default, O: Observer<T>> Tree<T, O> {
+    /// Renders the tree as a Graphviz digraph using the default [`DotOptions`].
+    pub fn to_dot(&self) -> String {
+        self.to_dot_with(&DotOptions::default())
+    }
+
+    /// Renders the tree as a Graphviz digraph. Red and black nodes are filled
+    /// with their color so the balancing is visible at a glance.
+    pub fn to_dot_with(&self, options: &DotOptions) -> String {
+        let parents = options.show_parent_edges.then(|| self.parent_links());
+        let mut out = String::new();
+        self.to_shape()
+            .fmt_dot(&mut out, options, None, parents.as_deref())
+            .expect("Writing to a String cannot fail");
+        out
+    }
+
+    /// Writes the Graphviz digraph produced by [`Tree::to_dot_with`] to `writer`.
+    #[cfg(feature = "std")]
+    pub fn write_dot<W: io::Write>(&self, writer: &mut W, options: &DotOptions) -> io::Result<()> {
+        writer.write_all(self.to_dot_with(options).as_bytes())
+    }
+
+    /// Reads the parent link of every node, in the pre-order that
+    /// [`DotWriter`] numbers the nodes in.
+    fn parent_links(&self) -> Vec<ParentLink> {
+        let mut nodes = Vec::new();
+        let mut stack = vec![self.root.clone()];
+        while let Some(node) = stack.pop() {
+            if node.borrow().is_nil() {
+                continue;
+            }
+            stack.push(node.borrow().right().clone());
+            stack.push(node.borrow().left().clone());
+            nodes.push(node);
+        }
+        let index: BTreeMap<*const RefCell<Node<T>>, usize> = nodes
+            .iter()
+            .enumerate()
+            .map(|(i, node)| (Rc::as_ptr(node), i))
+            .collect();
+
+        nodes
+            .iter()
+            .map(|node| match &node.borrow().parent {
+                Some(parent) if Rc::ptr_eq(parent, &self.sentinel) => ParentLink::Nil,
+                Some(parent) => match index.get(&Rc::as_ptr(parent)) {
+                    Some(&i) => ParentLink::Node(i),
+                    None => ParentLink::Missing,
+                },
+                None => ParentLink::Missing,
+            })
+            .collect()
+    }
+}
+
+impl<T: Debug> Shape<T> {
+    /// Renders the shape as a Graphviz digraph using the default
+    /// [`DotOptions`].
+    pub fn to_dot(&self) -> String {
+        self.to_dot_with(&DotOptions::default())
+    }
+
+    /// Renders the shape as a Graphviz digraph, like [`Tree::to_dot_with`].
+    pub fn to_dot_with(&self, options: &DotOptions) -> String {
+        let mut out = String::new();
+        self.fmt_dot(&mut out, options, None, None)
+            .expect("Writing to a String cannot fail");
+        out
+    }
+
+    /// Writes the digraph, with `caption` as the graph label if given, and
+    /// the parent edges given by `parents` if the options ask for them.
+    pub(super) fn fmt_dot<W: Write>(
+        &self,
+        out: &mut W,
+        options: &DotOptions,
+        caption: Option<&str>,
+        parents: Option<&[ParentLink]>,
+    ) -> fmt::Result {
+        writeln!(out, "digraph Tree {{")?;
+        writeln!(
+            out,
+            "    node [style=filled, fontcolor=white, fontname=\"Helvetica\"];"
+        )?;
+        if let Some(caption) = caption {
+            writeln!(out, "    label=\"{}\";", escape(caption))?;
+        }
+        if !self.is_nil() {
+            let mut writer = DotWriter {
+                out,
+                options,
+                parents,
+                next_id: 0,
+                next_nil_id: 0,
+            };
+            writer.write_node(self)?;
+        }
+        writeln!(out, "}}")
+    }
+}
+
+/// Emits nodes in pre-order so that identifiers, and therefore the output,
+/// are deterministic for a given tree shape.
+struct DotWriter<'a, W> {
+    out: &'a mut W,
+    options: &'a DotOptions,
+    parents: Option<&'a [ParentLink]>,
+    next_id: usize,
+    next_nil_id: usize,
+}
+
+impl<'a, W: Write> DotWriter<'a, W> {
+    /// Writes `node` and its subtree, returning the identifier assigned to `node`.
+    fn write_node<T: Debug>(&mut self, node: &Shape<T>) -> Result<String, fmt::Error> {
+        let Shape::Node {
+            key,
+            color,
+            left,
+            right,
+        } = node
+        else {
+            unreachable!("T.nil has no identifier");
+        };
+        let index = self.next_id;
+        let id = format!("n{}", index);
+        self.next_id += 1;
+
+        let fill = match color {
+            NodeColor::Red => "red",
+            NodeColor::Black => "black",
+        };
+        writeln!(
+            self.out,
+            "    {} [label=\"{}\", fillcolor={}];",
+            id,
+            escape(&format!("{:?}", key)),
+            fill
+        )?;
+        if let Some(parents) = self.parents {
+            self.write_parent_edge(&id, index, &parents[index])?;
+        }
+
+        for (child, sibling) in [(left, right), (right, left)] {
+            if !child.is_nil() {
+                let child_id = self.write_node(child)?;
+                writeln!(self.out, "    {} -> {};", id, child_id)?;
+            } else if self.options.show_nil {
+                let nil_id = self.next_nil_id();
+                writeln!(
+                    self.out,
+                    "    {} [label=\"NIL\", shape=box, fillcolor=black, fontsize=8];",
+                    nil_id
+                )?;
+                writeln!(self.out, "    {} -> {};", id, nil_id)?;
+            } else if !sibling.is_nil() {
+                // Graphviz has no notion of left and right children, so keep a
+                // lone child on its correct side with an invisible placeholder.
+                let nil_id = self.next_nil_id();
+                writeln!(self.out, "    {} [style=invis];", nil_id)?;
+                writeln!(self.out, "    {} -> {} [style=invis];", id, nil_id)?;
+            }
+        }
+
+        Ok(id)
+    }
+
+    /// Writes the edge from the node `id` with pre-order `index` to where its
+    /// parent link points, if anywhere but T.nil at the root.
+    fn write_parent_edge(&mut self, id: &str, index: usize, link: &ParentLink) -> fmt::Result {
+        let (target, marker) = match link {
+            ParentLink::Node(parent) => (format!("n{}", parent), None),
+            ParentLink::Nil if index == 0 => return Ok(()),
+            ParentLink::Nil => (format!("parent{}", index), Some("NIL")),
+            ParentLink::Missing => (format!("parent{}", index), Some("?")),
+        };
+        if let Some(label) = marker {
+            writeln!(
+                self.out,
+                "    {} [label=\"{}\", shape=box, fillcolor=gray, fontsize=8];",
+                target, label
+            )?;
+        }
+        writeln!(
+            self.out,
+            "    {} -> {} [style=dashed, color=gray, constraint=false];",
+            id, target
+        )
+    }
+
+    fn next_nil_id(&mut self) -> String {
+        let id = format!("nil{}", self.next_nil_id);
+        self.next_nil_id += 1;
+        id
+    }
+}
+
+fn escape(label: &str) -> String {
+    label.replace('\\', "\\\\").replace('"', "\\\"")
+}
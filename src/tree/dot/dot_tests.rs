@@ -0,0 +1,120 @@
+use crate::tree::{DotOptions, Tree};
+use std::string::ToString;
+
+#[test]
+fn test_to_dot_empty_tree() {
+    let tree: Tree<i32> = Tree::new();
+
+    assert_eq!(
+        tree.to_dot(),
+        "digraph Tree {\n    node [style=filled, fontcolor=white, fontname=\"Helvetica\"];\n}\n"
+    );
+}
+
+#[test]
+fn test_to_dot_colors_and_edges() {
+    let mut tree = Tree::new();
+    tree.insert(5);
+    tree.insert(15);
+    tree.insert(2);
+
+    let dot = tree.to_dot();
+
+    assert!(dot.contains("    n0 [label=\"5\", fillcolor=black];\n"));
+    assert!(dot.contains("    n1 [label=\"2\", fillcolor=red];\n"));
+    assert!(dot.contains("    n2 [label=\"15\", fillcolor=red];\n"));
+    assert!(dot.contains("    n0 -> n1;\n"));
+    assert!(dot.contains("    n0 -> n2;\n"));
+    assert!(!dot.contains("NIL"));
+    assert!(!dot.contains("dashed"));
+}
+
+#[test]
+fn test_to_dot_with_nil_and_parent_edges() {
+    let mut tree = Tree::new();
+    tree.insert(5);
+    tree.insert(15);
+
+    let dot = tree.to_dot_with(&DotOptions {
+        show_nil: true,
+        show_parent_edges: true,
+    });
+
+    // 5 has a nil left child, 15 has two nil children.
+    assert_eq!(dot.matches("label=\"NIL\"").count(), 3);
+    assert!(dot.contains("    n1 -> n0 [style=dashed, color=gray, constraint=false];\n"));
+}
+
+#[test]
+fn test_parent_edges_follow_broken_parent_links() {
+    let mut tree = Tree::new();
+    for key in 1..=3 {
+        tree.insert(key);
+    }
+    let options = DotOptions {
+        show_nil: false,
+        show_parent_edges: true,
+    };
+    let left = tree.root.borrow().left().clone();
+    let right = tree.root.borrow().right().clone();
+
+    // Point 3 at its sibling 1 instead of at 2, then at nothing at all.
+    right.borrow_mut().parent = Some(left.clone());
+    let dot = tree.to_dot_with(&options);
+    assert!(dot.contains("    n2 -> n1 [style=dashed, color=gray, constraint=false];\n"));
+    assert!(!dot.contains("    n2 -> n0 [style=dashed"));
+
+    right.borrow_mut().parent = None;
+    let dot = tree.to_dot_with(&options);
+    assert!(dot.contains("    parent2 [label=\"?\", shape=box, fillcolor=gray, fontsize=8];\n"));
+    assert!(dot.contains("    n2 -> parent2 [style=dashed, color=gray, constraint=false];\n"));
+
+    right.borrow_mut().parent = Some(tree.root.clone());
+    let dot = tree.to_dot_with(&options);
+    assert!(dot.contains("    n1 -> n0 [style=dashed"));
+    assert!(dot.contains("    n2 -> n0 [style=dashed"));
+    assert!(!dot.contains("parent"));
+}
+
+#[test]
+fn test_to_dot_keeps_lone_child_on_its_side() {
+    let mut tree = Tree::new();
+    tree.insert(5);
+    tree.insert(15);
+
+    let dot = tree.to_dot();
+
+    // The missing left child of 5 is drawn invisibly before its right child.
+    let placeholder = dot.find("nil0 [style=invis]").unwrap();
+    let right_child = dot.find("n1 [label=\"15\"").unwrap();
+    assert!(placeholder < right_child);
+}
+
+#[test]
+fn test_to_dot_escapes_labels() {
+    let mut tree = Tree::new();
+    tree.insert("say \"hi\"".to_string());
+
+    assert!(tree
+        .to_dot()
+        .contains(r#"n0 [label="\"say \\\"hi\\\"\"", fillcolor=black];"#));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_write_dot_matches_to_dot() {
+    use std::{string::String, vec};
+
+    let mut tree = Tree::new();
+    tree.insert(1);
+    tree.insert(2);
+    let options = DotOptions::default();
+
+    let mut bytes = vec![];
+    tree.write_dot(&mut bytes, &options).unwrap();
+
+    assert_eq!(
+        String::from_utf8(bytes).unwrap(),
+        tree.to_dot_with(&options)
+    );
+}
@@ -0,0 +1,178 @@
+use crate::{
+    node::Node,
+    tree::{NoopObserver, Observer, Tree},
+};
+use alloc::rc::Rc;
+use core::{
+    cell::RefCell,
+    fmt::Debug,
+    ops::{Bound, RangeBounds, RangeFull},
+};
+
+#[cfg(test)]
+mod extract_tests;
+
+/// An iterator that removes and yields every element of a [`Tree`] in
+/// ascending order. Created by [`Tree::drain`].
+///
+/// The tree is empty once the iterator is dropped, even if it was not fully
+/// consumed.
+pub struct Drain<
+    'a,
+    T: PartialOrd + Clone + PartialEq + Debug + Default,
+    O: Observer<T> = NoopObserver,
+> {
+    tree: &'a mut Tree<T, O>,
+    next: Option<Rc<RefCell<Node<T>>>>,
+}
+
+/// An iterator that removes and yields the elements within a range that match
+/// a predicate, in ascending order. Created by [`Tree::extract_if`].
+///
+/// Elements that have not been visited when the iterator is dropped are kept.
+pub struct ExtractIf<'a, T, R, F, O = NoopObserver> {
+    tree: &'a mut Tree<T, O>,
+    next: Option<Rc<RefCell<Node<T>>>>,
+    range: R,
+    pred: F,
+}
+
+impl<T: PartialOrd + Clone + PartialEq + Debug + Default, O: Observer<T>> Tree<T, O> {
+    /// Keeps only the elements for which `f` returns `true`, visiting every
+    /// element once in ascending order.
+    pub fn retain<F: FnMut(&T) -> bool>(&mut self, mut f: F) {
+        let mut next = self.range_start_node::<RangeFull>(&..);
+        let mut pred = |key: &T| !f(key);
+        while self.extract_next(&mut next, &.., &mut pred).is_some() {}
+        self.check_invariants("retain", None);
+    }
+
+    /// Removes every element, yielding them in ascending order.
+    pub fn drain(&mut self) -> Drain<'_, T, O> {
+        let next = self.range_start_node::<RangeFull>(&..);
+        Drain { tree: self, next }
+    }
+
+    /// Returns an iterator that removes and yields the elements within `range`
+    /// for which `pred` returns `true`.
+    pub fn extract_if<R, F>(&mut self, range: R, pred: F) -> ExtractIf<'_, T, R, F, O>
+    where
+        R: RangeBounds<T>,
+        F: FnMut(&T) -> bool,
+    {
+        let next = self.range_start_node(&range);
+        ExtractIf {
+            tree: self,
+            next,
+            range,
+            pred,
+        }
+    }
+
+    /// Removes every element within `range` and returns how many were removed.
+    pub fn remove_range<R: RangeBounds<T>>(&mut self, range: R) -> usize {
+        let mut next = self.range_start_node(&range);
+        let mut removed = 0;
+        while self
+            .extract_next(&mut next, &range, &mut |_| true)
+            .is_some()
+        {
+            removed += 1;
+        }
+        self.check_invariants("remove_range", None);
+        removed
+    }
+
+    /// Returns the node holding the smallest key within `range`, ignoring its
+    /// end bound.
+    fn range_start_node<R: RangeBounds<T>>(&self, range: &R) -> Option<Rc<RefCell<Node<T>>>> {
+        match range.start_bound() {
+            Bound::Included(key) => self.lower_bound_node(key),
+            Bound::Excluded(key) => self.upper_bound_node(key),
+            Bound::Unbounded => {
+                if self.root.borrow().is_nil() {
+                    None
+                } else {
+                    self.minimum_node(self.root.clone())
+                }
+            }
+        }
+    }
+
+    /// Walks forward from `next` and removes the first node within the end
+    /// bound of `range` whose key matches `pred`. `next` is left at the node
+    /// following the removed one, or `None` once the walk leaves `range`.
+    fn extract_next<R, F>(
+        &mut self,
+        next: &mut Option<Rc<RefCell<Node<T>>>>,
+        range: &R,
+        pred: &mut F,
+    ) -> Option<Rc<RefCell<Node<T>>>>
+    where
+        R: RangeBounds<T>,
+        F: FnMut(&T) -> bool,
+    {
+        while let Some(node) = next.take() {
+            let within_range = match range.end_bound() {
+                Bound::Included(end) => node.borrow().key <= *end,
+                Bound::Excluded(end) => node.borrow().key < *end,
+                Bound::Unbounded => true,
+            };
+            if !within_range {
+                return None;
+            }
+
+            // The CLRS deletion relinks nodes instead of moving keys around,
+            // so the successor stays valid after `node` is removed.
+            *next = self.successor_node(node.clone());
+            if pred(&node.borrow().key) {
+                self.delete_node(node.clone());
+                self.length -= 1;
+                return Some(node);
+            }
+        }
+        None
+    }
+}
+
+impl<T: PartialOrd + Clone + PartialEq + Debug + Default, O: Observer<T>> Iterator
+    for Drain<'_, T, O>
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        let node = self.tree.extract_next(&mut self.next, &.., &mut |_| true)?;
+        let key = node.borrow().key.clone();
+        self.tree.check_invariants("drain", Some(&key));
+        Some(key)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.tree.len(), Some(self.tree.len()))
+    }
+}
+
+impl<T: PartialOrd + Clone + PartialEq + Debug + Default, O: Observer<T>> Drop for Drain<'_, T, O> {
+    fn drop(&mut self) {
+        self.tree.clear();
+    }
+}
+
+impl<T, R, F, O> Iterator for ExtractIf<'_, T, R, F, O>
+where
+    T: PartialOrd + Clone + PartialEq + Debug + Default,
+    R: RangeBounds<T>,
+    F: FnMut(&T) -> bool,
+    O: Observer<T>,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        let node = self
+            .tree
+            .extract_next(&mut self.next, &self.range, &mut self.pred)?;
+        let key = node.borrow().key.clone();
+        self.tree.check_invariants("extract_if", Some(&key));
+        Some(key)
+    }
+}
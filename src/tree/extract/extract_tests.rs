@@ -0,0 +1,149 @@
+use crate::tree::{tree_tests::assert_red_black_tree_properties, Tree};
+use proptest::prelude::*;
+use std::{collections::BTreeSet, format, rc::Rc, vec, vec::Vec};
+
+fn keys_of(tree: &Tree<i32>) -> Vec<i32> {
+    let mut cursor = tree.lower_bound_cursor(&i32::MIN);
+    let mut keys = vec![];
+    while let Some(key) = cursor.peek() {
+        keys.push(*key);
+        cursor.move_next();
+    }
+    keys
+}
+
+#[test]
+fn test_retain() {
+    let mut tree: Tree<i32> = (0..20).collect();
+
+    tree.retain(|key| key % 3 == 0);
+
+    assert_eq!(keys_of(&tree), vec![0, 3, 6, 9, 12, 15, 18]);
+    assert_eq!(tree.len(), 7);
+    assert_red_black_tree_properties(&tree);
+}
+
+#[test]
+fn test_retain_visits_each_element_once_in_order() {
+    let mut tree: Tree<i32> = [5, 1, 4, 2, 3].into_iter().collect();
+    let mut visited = vec![];
+
+    tree.retain(|key| {
+        visited.push(*key);
+        false
+    });
+
+    assert_eq!(visited, vec![1, 2, 3, 4, 5]);
+    assert!(tree.is_empty());
+}
+
+#[test]
+fn test_drain() {
+    let mut tree: Tree<i32> = [3, 1, 2].into_iter().collect();
+
+    assert_eq!(tree.drain().collect::<Vec<_>>(), vec![1, 2, 3]);
+    assert!(tree.is_empty());
+
+    tree.insert(4);
+    assert_eq!(keys_of(&tree), vec![4]);
+}
+
+#[test]
+fn test_drain_dropped_early_empties_tree() {
+    let mut tree: Tree<i32> = (0..10).collect();
+
+    let mut drain = tree.drain();
+    assert_eq!(drain.size_hint(), (10, Some(10)));
+    assert_eq!(drain.next(), Some(0));
+    drop(drain);
+
+    assert!(tree.is_empty());
+    assert_eq!(tree.len(), 0);
+}
+
+#[test]
+fn test_extract_if() {
+    let mut tree: Tree<i32> = (0..20).collect();
+
+    let extracted: Vec<_> = tree.extract_if(5..15, |key| key % 2 == 0).collect();
+
+    assert_eq!(extracted, vec![6, 8, 10, 12, 14]);
+    assert_eq!(tree.len(), 15);
+    assert_eq!(
+        keys_of(&tree),
+        vec![0, 1, 2, 3, 4, 5, 7, 9, 11, 13, 15, 16, 17, 18, 19]
+    );
+    assert_red_black_tree_properties(&tree);
+}
+
+#[test]
+fn test_extract_if_is_lazy() {
+    let mut tree: Tree<i32> = (0..10).collect();
+
+    let mut extract = tree.extract_if(.., |_| true);
+    assert_eq!(extract.next(), Some(0));
+    assert_eq!(extract.next(), Some(1));
+    drop(extract);
+
+    assert_eq!(keys_of(&tree), (2..10).collect::<Vec<_>>());
+}
+
+#[test]
+fn test_remove_range() {
+    let mut tree: Tree<i32> = (0..10).collect();
+
+    assert_eq!(tree.remove_range(3..=6), 4);
+    assert_eq!(keys_of(&tree), vec![0, 1, 2, 7, 8, 9]);
+
+    assert_eq!(tree.remove_range(..2), 2);
+    assert_eq!(tree.remove_range(8..), 2);
+    assert_eq!(tree.remove_range(100..), 0);
+    assert_eq!(keys_of(&tree), vec![2, 7]);
+    assert_eq!(tree.len(), 2);
+}
+
+#[test]
+fn test_delete_missing_key_keeps_length() {
+    let mut tree: Tree<i32> = [1, 2].into_iter().collect();
+
+    tree.delete(3);
+
+    assert_eq!(tree.len(), 2);
+}
+
+#[test]
+fn test_clear_and_drop_free_nodes() {
+    let mut tree: Tree<i32> = (0..100).collect();
+    let sentinel = Rc::downgrade(&tree.sentinel);
+    let root = Rc::downgrade(&tree.root);
+
+    tree.clear();
+    assert!(root.upgrade().is_none());
+
+    tree.insert(1);
+    let root = Rc::downgrade(&tree.root);
+    drop(tree);
+    assert!(root.upgrade().is_none());
+    assert!(sentinel.upgrade().is_none());
+}
+
+proptest! {
+    #[test]
+    fn test_remove_range_empirical(
+        keys in prop::collection::btree_set(0i32..1000, 0..100),
+        start in 0i32..1000,
+        len in 0i32..500,
+    ) {
+        let mut tree: Tree<i32> = keys.iter().copied().collect();
+        let mut expected: BTreeSet<i32> = keys.clone();
+        let removed = tree.remove_range(start..start + len);
+
+        expected.retain(|key| !(start..start + len).contains(key));
+        prop_assert_eq!(removed, keys.len() - expected.len());
+        prop_assert_eq!(tree.len(), expected.len());
+        prop_assert_eq!(keys_of(&tree), expected.into_iter().collect::<Vec<_>>());
+        if !tree.is_empty() {
+            assert_red_black_tree_properties(&tree);
+        }
+    }
+}
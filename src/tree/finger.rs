@@ -0,0 +1,87 @@
+use crate::{
+    node::Node,
+    tree::{compare, Handle, Observer, Position, Tree},
+};
+use alloc::rc::Rc;
+use core::{cell::RefCell, cmp::Ordering, fmt::Debug};
+
+#[cfg(test)]
+mod finger_tests;
+
+/// Finger search: lookups and insertions that start from the element a
+/// [`Handle`] refers to instead of the root.
+///
+/// The search climbs the parent links from the finger to the lowest ancestor
+/// whose subtree must contain the key, then descends from there. Keys that
+/// are close to the finger are found without visiting the upper levels of the
+/// tree: for keys that are `d` elements apart the climb usually takes
+/// `O(log d)` steps, and a scan that moves the finger along takes amortized
+/// `O(1)` per key. The climb may still reach the root when the finger and
+/// the key lie on either side of the boundary between two large subtrees.
+///
+/// A handle that no longer refers to an element of the tree is not an error,
+/// the search then starts from the root.
+impl<T: PartialOrd + Clone + PartialEq + Debug + Default, O: Observer<T>> Tree<T, O> {
+    /// Returns `true` if the tree contains `key`, searching from `finger`.
+    pub fn contains_near(&self, finger: &Handle<T>, key: &T) -> bool {
+        self.find_near(finger, key).is_some()
+    }
+
+    /// Returns a handle to the element equal to `key`, searching from
+    /// `finger`. The result serves as the finger for the next search.
+    pub fn find_near(&self, finger: &Handle<T>, key: &T) -> Option<Handle<T>> {
+        match self.position_near(finger, key) {
+            Position::Found(node) => Some(self.handle_of(&node)),
+            Position::Vacant { .. } => None,
+        }
+    }
+
+    /// Inserts `key` like [`Tree::insert_with_handle`], searching for its
+    /// position from `hint`. Returns `None` and leaves the tree unchanged if
+    /// an equal key is already present.
+    pub fn insert_with_hint(&mut self, hint: &Handle<T>, key: T) -> Option<Handle<T>> {
+        let Position::Vacant { parent, as_left } = self.position_near(hint, &key) else {
+            return None;
+        };
+        let z = self.insert_at(parent, as_left, key);
+        self.check_invariants("insert_with_hint", Some(&z.borrow().key));
+        Some(self.handle_of(&z))
+    }
+
+    fn position_near(&self, finger: &Handle<T>, key: &T) -> Position<T> {
+        let start = match self.resolve(finger) {
+            Some(node) => self.climb(node, key),
+            None => self.root.clone(),
+        };
+        self.position_from(start, |other| compare(key, other))
+    }
+
+    /// Climbs from `node` to the lowest ancestor, `node` included, whose
+    /// subtree contains the position of `key`.
+    fn climb(&self, mut node: Rc<RefCell<Node<T>>>, key: &T) -> Rc<RefCell<Node<T>>> {
+        let direction = compare(key, &node.borrow().key);
+        if direction == Ordering::Equal {
+            return node;
+        }
+        loop {
+            let parent = node.borrow().parent().clone();
+            if parent.borrow().is_nil() {
+                return node;
+            }
+            // The subtree of a left child ends before its parent and the
+            // subtree of a right child starts after it. Only a parent on the
+            // side of `key` can end the climb.
+            let is_left = Rc::ptr_eq(&node, parent.borrow().left());
+            if is_left == (direction == Ordering::Greater) {
+                let ordering = compare(key, &parent.borrow().key);
+                if ordering == Ordering::Equal {
+                    return parent;
+                }
+                if ordering != direction {
+                    return node;
+                }
+            }
+            node = parent;
+        }
+    }
+}
@@ -0,0 +1,162 @@
+use crate::tree::{tree_tests::assert_red_black_tree_properties, Tree};
+use core::{cell::Cell, cmp::Ordering};
+use proptest::prelude::*;
+use std::{format, thread_local, vec::Vec};
+
+thread_local! {
+    static COMPARISONS: Cell<usize> = const { Cell::new(0) };
+}
+
+/// A key that counts how often it is compared.
+#[derive(Debug, Clone, Copy, Default)]
+struct Counted(i32);
+
+impl PartialEq for Counted {
+    fn eq(&self, other: &Self) -> bool {
+        COMPARISONS.with(|count| count.set(count.get() + 1));
+        self.0 == other.0
+    }
+}
+
+impl PartialOrd for Counted {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        COMPARISONS.with(|count| count.set(count.get() + 1));
+        self.0.partial_cmp(&other.0)
+    }
+}
+
+fn comparisons() -> usize {
+    COMPARISONS.with(Cell::get)
+}
+
+#[test]
+fn test_contains_near() {
+    let mut tree = Tree::new();
+    for key in (0..100).step_by(2) {
+        tree.insert(key);
+    }
+    let finger = tree.insert_with_handle(51).unwrap();
+
+    for key in 0..100 {
+        assert_eq!(tree.contains_near(&finger, &key), key % 2 == 0 || key == 51);
+    }
+    assert!(!tree.contains_near(&finger, &-1));
+    assert!(!tree.contains_near(&finger, &100));
+}
+
+#[test]
+fn test_find_near_moves_the_finger() {
+    let tree: Tree<i32> = (0..50).collect();
+    let mut finger = tree.find_near(&tree_handle(&tree, 0), &0).unwrap();
+
+    for key in 1..50 {
+        finger = tree.find_near(&finger, &key).unwrap();
+        assert_eq!(tree.get(&finger), Some(&key));
+    }
+    assert!(tree.find_near(&finger, &50).is_none());
+}
+
+#[test]
+fn test_insert_with_hint() {
+    let mut tree = Tree::new();
+    let mut hint = tree.insert_with_handle(0).unwrap();
+    for key in 1..200 {
+        hint = tree.insert_with_hint(&hint, key).unwrap();
+    }
+    // Far away from the hint on both sides.
+    tree.insert_with_hint(&hint, -1000).unwrap();
+    let first = tree.find_near(&hint, &0).unwrap();
+    tree.insert_with_hint(&first, 1000).unwrap();
+
+    assert_eq!(tree.len(), 202);
+    assert!(tree
+        .iter()
+        .copied()
+        .eq((-1000..-999).chain(0..200).chain(1000..1001)));
+    assert_red_black_tree_properties(&tree);
+}
+
+#[test]
+fn test_insert_with_hint_rejects_duplicates() {
+    let mut tree: Tree<i32> = (0..10).collect();
+    let hint = tree.find_near(&tree_handle(&tree, 5), &5).unwrap();
+
+    assert!(tree.insert_with_hint(&hint, 3).is_none());
+    assert_eq!(tree.len(), 10);
+}
+
+#[test]
+fn test_stale_finger_searches_from_root() {
+    let mut tree: Tree<i32> = (0..10).collect();
+    let finger = tree.insert_with_handle(10).unwrap();
+    tree.delete(10);
+
+    assert!(tree.contains_near(&finger, &3));
+    assert!(!tree.contains_near(&finger, &10));
+    assert!(tree.insert_with_hint(&finger, 11).is_some());
+    assert_red_black_tree_properties(&tree);
+}
+
+#[test]
+fn test_sequential_scan_is_cheaper_than_root_searches() {
+    let tree: Tree<Counted> = (0..4096).map(Counted).collect();
+
+    let before = comparisons();
+    for key in 0..4096 {
+        assert!(tree.contains_key(Counted(key)));
+    }
+    let from_root = comparisons() - before;
+
+    let mut finger = tree_handle(&tree, Counted(0));
+    let before = comparisons();
+    for key in 0..4096 {
+        finger = tree.find_near(&finger, &Counted(key)).unwrap();
+    }
+    let near = comparisons() - before;
+
+    assert!(near * 2 < from_root, "{near} vs. {from_root}");
+}
+
+/// Returns a handle to `key`, found by a search from the root.
+fn tree_handle<T>(tree: &Tree<T>, key: T) -> crate::tree::Handle<T>
+where
+    T: PartialOrd + Clone + PartialEq + core::fmt::Debug + Default,
+{
+    let node = tree.search(key).unwrap();
+    tree.handle_of(&node)
+}
+
+proptest! {
+    #[test]
+    fn test_matches_contains_key(
+        keys in prop::collection::vec(-100i32..100, 1..100),
+        fingers in prop::collection::vec(any::<prop::sample::Index>(), 1..10),
+    ) {
+        let tree: Tree<i32> = keys.iter().copied().collect();
+        let present: Vec<_> = tree.iter().copied().collect();
+        for finger in fingers {
+            let finger = tree_handle(&tree, *finger.get(&present));
+            for key in -101..101 {
+                prop_assert_eq!(tree.contains_near(&finger, &key), tree.contains_key(key));
+            }
+        }
+    }
+
+    #[test]
+    fn test_hinted_inserts_stay_valid(
+        keys in prop::collection::vec(-100i32..100, 1..100),
+    ) {
+        let mut tree = Tree::new();
+        let mut hint = tree.insert_with_handle(keys[0]).unwrap();
+        for &key in &keys[1..] {
+            if let Some(handle) = tree.insert_with_hint(&hint, key) {
+                hint = handle;
+            }
+            prop_assert_eq!(tree.validate(), Ok(()));
+        }
+        let mut expected = keys.clone();
+        expected.sort();
+        expected.dedup();
+        prop_assert!(tree.iter().eq(expected.iter()));
+    }
+}
@@ -0,0 +1,100 @@
+use crate::{
+    node::Node,
+    tree::{Observer, Tree},
+};
+use alloc::rc::{Rc, Weak};
+use core::{cell::RefCell, fmt::Debug};
+
+#[cfg(test)]
+mod handle_tests;
+
+/// An opaque reference to an element of a [`Tree`], returned by
+/// [`Tree::insert_with_handle`].
+///
+/// A handle reaches its element without searching from the root. It does not
+/// keep the element alive: once the element is removed, or the handle is used
+/// with another tree, every method taking it returns `None`.
+pub struct Handle<T> {
+    node: Weak<RefCell<Node<T>>>,
+    /// The generation the node was stamped with when it was inserted.
+    generation: u64,
+    /// The T.nil of the tree the node was inserted into.
+    sentinel: Weak<RefCell<Node<T>>>,
+}
+
+impl<T> Clone for Handle<T> {
+    fn clone(&self) -> Self {
+        Self {
+            node: self.node.clone(),
+            generation: self.generation,
+            sentinel: self.sentinel.clone(),
+        }
+    }
+}
+
+impl<T> Debug for Handle<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Handle")
+            .field("node", &self.node.as_ptr())
+            .field("generation", &self.generation)
+            .finish()
+    }
+}
+
+impl<T: PartialOrd + Clone + PartialEq + Debug + Default, O: Observer<T>> Tree<T, O> {
+    /// Inserts `key` like [`Tree::insert`] and returns a handle to the new
+    /// element, or `None` if an equal key is already present.
+    pub fn insert_with_handle(&mut self, key: T) -> Option<Handle<T>> {
+        let z = self.insert_node(key).ok()?;
+        self.check_invariants("insert_with_handle", Some(&z.borrow().key));
+        Some(self.handle_of(&z))
+    }
+
+    /// Returns the element `handle` refers to, or `None` if it was removed.
+    pub fn get(&self, handle: &Handle<T>) -> Option<&T> {
+        let node = self.resolve(handle)?;
+        Some(self.key_ref(&node))
+    }
+
+    /// Removes the element `handle` refers to without searching for it.
+    /// Returns `None` and does nothing if it was already removed.
+    pub fn remove_by_handle(&mut self, handle: &Handle<T>) -> Option<T> {
+        let node = self.resolve(handle)?;
+        self.delete_node(node.clone());
+        self.length -= 1;
+        let key = node.borrow().key.clone();
+        self.check_invariants("remove_by_handle", Some(&key));
+        Some(key)
+    }
+
+    /// Returns a handle to the element following the one `handle` refers to,
+    /// or `None` if it is the largest element or was removed.
+    pub fn successor(&self, handle: &Handle<T>) -> Option<Handle<T>> {
+        let node = self.resolve(handle)?;
+        self.successor_node(node).map(|next| self.handle_of(&next))
+    }
+
+    pub(super) fn handle_of(&self, node: &Rc<RefCell<Node<T>>>) -> Handle<T> {
+        Handle {
+            node: Rc::downgrade(node),
+            generation: node.borrow().generation,
+            sentinel: Rc::downgrade(&self.sentinel),
+        }
+    }
+
+    /// Returns the node `handle` refers to if it is still part of this tree.
+    ///
+    /// Removed nodes are either freed, so that the weak reference is dead, or
+    /// still held elsewhere with their generation reset to 0. A live node
+    /// with the stamped generation and the same T.nil belongs to this tree.
+    pub(super) fn resolve(&self, handle: &Handle<T>) -> Option<Rc<RefCell<Node<T>>>> {
+        if !Weak::ptr_eq(&handle.sentinel, &Rc::downgrade(&self.sentinel)) {
+            return None;
+        }
+        let node = handle.node.upgrade()?;
+        if node.borrow().generation != handle.generation {
+            return None;
+        }
+        Some(node)
+    }
+}
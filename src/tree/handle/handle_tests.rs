@@ -0,0 +1,146 @@
+use crate::tree::{tree_tests::assert_red_black_tree_properties, Tree};
+use std::vec::Vec;
+
+#[test]
+fn test_get_by_handle() {
+    let mut tree = Tree::new();
+    let handles: Vec<_> = (0..20)
+        .map(|key| tree.insert_with_handle(key).unwrap())
+        .collect();
+
+    for (key, handle) in handles.iter().enumerate() {
+        assert_eq!(tree.get(handle), Some(&(key as i32)));
+    }
+    assert_red_black_tree_properties(&tree);
+}
+
+#[test]
+fn test_remove_by_handle() {
+    let mut tree = Tree::new();
+    let handles: Vec<_> = (0..20)
+        .map(|key| tree.insert_with_handle(key).unwrap())
+        .collect();
+
+    for handle in handles.iter().step_by(2) {
+        assert!(tree.remove_by_handle(handle).is_some());
+        assert_red_black_tree_properties(&tree);
+    }
+
+    assert_eq!(tree.len(), 10);
+    assert!(tree.iter().all(|key| key % 2 == 1));
+    for handle in handles.iter().skip(1).step_by(2) {
+        assert!(tree.get(handle).is_some());
+    }
+}
+
+#[test]
+fn test_handles_survive_rebalancing() {
+    let mut tree = Tree::new();
+    let handle = tree.insert_with_handle(50).unwrap();
+    for key in 0..100 {
+        tree.insert(key);
+    }
+    for key in 0..50 {
+        tree.delete(key);
+    }
+
+    assert_eq!(tree.get(&handle), Some(&50));
+    assert_eq!(tree.remove_by_handle(&handle), Some(50));
+    assert!(!tree.contains_key(50));
+}
+
+#[test]
+fn test_no_handle_for_duplicate() {
+    let mut tree = Tree::new();
+    tree.insert(1);
+
+    assert!(tree.insert_with_handle(1).is_none());
+    assert_eq!(tree.len(), 1);
+}
+
+#[test]
+fn test_stale_handle_after_removal() {
+    let mut tree = Tree::new();
+    let handle = tree.insert_with_handle(1).unwrap();
+    tree.insert(2);
+
+    tree.delete(1);
+
+    assert_eq!(tree.get(&handle), None);
+    assert_eq!(tree.remove_by_handle(&handle), None);
+    assert!(tree.successor(&handle).is_none());
+    assert_eq!(tree.len(), 1);
+}
+
+#[test]
+fn test_stale_handle_to_node_kept_alive() {
+    let mut tree = Tree::new();
+    let handle = tree.insert_with_handle(1).unwrap();
+    let node = handle.node.upgrade().unwrap();
+
+    assert_eq!(tree.remove_by_handle(&handle), Some(1));
+
+    assert_eq!(tree.get(&handle), None);
+    assert_eq!(node.borrow().generation, 0);
+}
+
+#[test]
+fn test_stale_handle_after_clear() {
+    let mut tree = Tree::new();
+    let handle = tree.insert_with_handle(1).unwrap();
+
+    tree.clear();
+    tree.insert(1);
+
+    assert_eq!(tree.get(&handle), None);
+}
+
+#[test]
+fn test_handle_from_other_tree() {
+    let mut tree = Tree::new();
+    let mut other = Tree::new();
+    tree.insert_with_handle(1).unwrap();
+    let handle = other.insert_with_handle(1).unwrap();
+
+    assert_eq!(tree.get(&handle), None);
+    assert_eq!(tree.remove_by_handle(&handle), None);
+    assert_eq!(tree.len(), 1);
+}
+
+#[test]
+fn test_successor() {
+    let mut tree = Tree::new();
+    let handles: Vec<_> = [5, 1, 3]
+        .map(|key| tree.insert_with_handle(key).unwrap())
+        .to_vec();
+
+    let next = tree.successor(&handles[1]).unwrap();
+    assert_eq!(tree.get(&next), Some(&3));
+    let next = tree.successor(&next).unwrap();
+    assert_eq!(tree.get(&next), Some(&5));
+    assert!(tree.successor(&next).is_none());
+}
+
+#[test]
+fn test_handles_do_not_resolve_in_split_off_tree() {
+    for index in [3, 17] {
+        let mut tree = Tree::new();
+        let handles: Vec<_> = (0..20)
+            .map(|key| tree.insert_with_handle(key).unwrap())
+            .collect();
+        let back = tree.split_off_at(index);
+
+        for (key, handle) in handles.iter().enumerate() {
+            let (owner, other) = if key < index {
+                (&tree, &back)
+            } else {
+                (&back, &tree)
+            };
+            assert!(other.get(handle).is_none());
+            // The larger half keeps the old T.nil and with it the handles.
+            if owner.len() >= other.len() {
+                assert_eq!(owner.get(handle), Some(&(key as i32)));
+            }
+        }
+    }
+}
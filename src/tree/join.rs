@@ -0,0 +1,232 @@
+use crate::{
+    node::{Node, NodeColor},
+    tree::{Observer, Tree},
+};
+use alloc::{rc::Rc, vec};
+use core::{cell::RefCell, fmt::Debug, mem};
+
+/// Concatenation and splitting by in-order position. Like the positional
+/// access in `order`, these ignore how the keys order.
+impl<T: PartialOrd + Clone + PartialEq + Debug + Default, O: Observer<T>> Tree<T, O> {
+    /// Appends the elements of `other` after the elements of the tree.
+    ///
+    /// Takes O(log n) time if both trees share T.nil. Otherwise the leaves of
+    /// the shorter tree are first relinked to the T.nil of the longer one,
+    /// which takes time linear in the shorter tree.
+    pub(crate) fn concat(&mut self, mut other: Tree<T, O>) {
+        self.generation = self.generation.max(other.generation);
+        if self.root.borrow().is_nil() {
+            mem::swap(&mut self.root, &mut other.root);
+            mem::swap(&mut self.sentinel, &mut other.sentinel);
+            mem::swap(&mut self.length, &mut other.length);
+        }
+        if !Rc::ptr_eq(&self.sentinel, &other.sentinel) {
+            if other.length <= self.length {
+                relink(&other.root, &self.sentinel, &mut self.generation);
+                other.sentinel = self.sentinel.clone();
+            } else {
+                relink(&self.root, &other.sentinel, &mut self.generation);
+                self.sentinel = other.sentinel.clone();
+            }
+        }
+
+        if let Some(pivot) = other.first_node() {
+            other.delete_node(pivot.clone());
+            self.generation += 1;
+            pivot.borrow_mut().generation = self.generation;
+            let left = self.root.clone();
+            let right = mem::replace(&mut other.root, other.sentinel.clone());
+            self.join(left, pivot, right);
+            self.length += other.length;
+            other.length = 0;
+        }
+        self.check_structure("concat");
+    }
+
+    /// Splits the tree at in-order position `index` and returns the elements
+    /// from `index` on as a new tree. `index` must not exceed the length.
+    ///
+    /// The split joins O(log n) pieces and measures the black height of each
+    /// by walking down a spine, which takes O(log² n) time. Handles tell
+    /// trees apart by their T.nil, so the smaller half is then relinked to a
+    /// T.nil of its own, in time linear in its size.
+    pub(crate) fn split_off_at(&mut self, index: usize) -> Tree<T, O>
+    where
+        O: Default,
+    {
+        let root = mem::replace(&mut self.root, self.sentinel.clone());
+        let (left, right) = self.split(root, index);
+
+        let mut other = Tree::with_observer(O::default());
+        other.augmentation = self.augmentation;
+        other.generation = self.generation;
+        if left.borrow().size < right.borrow().size {
+            mem::swap(&mut self.sentinel, &mut other.sentinel);
+            relink(&left, &self.sentinel, &mut self.generation);
+        } else {
+            relink(&right, &other.sentinel, &mut other.generation);
+        }
+        for (tree, root) in [(&mut *self, left), (&mut other, right)] {
+            tree.length = root.borrow().size;
+            tree.root = if root.borrow().is_nil() {
+                tree.sentinel.clone()
+            } else {
+                root
+            };
+        }
+
+        self.check_structure("split_off_at");
+        other.check_structure("split_off_at");
+        other
+    }
+
+    /// Splits the subtree rooted at `node` into the roots of the nodes before
+    /// in-order position `index` and the nodes from `index` on.
+    #[allow(clippy::type_complexity)]
+    fn split(
+        &mut self,
+        node: Rc<RefCell<Node<T>>>,
+        index: usize,
+    ) -> (Rc<RefCell<Node<T>>>, Rc<RefCell<Node<T>>>) {
+        if node.borrow().is_nil() {
+            return (node.clone(), node);
+        }
+        self.push_down(&node);
+        let left = node.borrow().left().clone();
+        let right = node.borrow().right().clone();
+        for child in [&left, &right] {
+            if !child.borrow().is_nil() {
+                child.borrow_mut().set_parent(self.sentinel.clone());
+            }
+        }
+
+        let left_size = left.borrow().size;
+        if index <= left_size {
+            let (before, after) = self.split(left, index);
+            (before, self.join(after, node, right))
+        } else {
+            let (before, after) = self.split(right, index - left_size - 1);
+            (self.join(left, node, before), after)
+        }
+    }
+
+    /// Links the detached subtrees `left` and `right` as the nodes before and
+    /// after `pivot`, restores the red-black properties and returns the root
+    /// of the result.
+    ///
+    /// `pivot` is attached red to the spine of the taller subtree, at the
+    /// first black node whose black height equals that of the shorter one,
+    /// which leaves at most a red node with a red parent for
+    /// [`Tree::insert_fix_up`] to repair.
+    fn join(
+        &mut self,
+        left: Rc<RefCell<Node<T>>>,
+        pivot: Rc<RefCell<Node<T>>>,
+        right: Rc<RefCell<Node<T>>>,
+    ) -> Rc<RefCell<Node<T>>> {
+        for root in [&left, &right] {
+            self.recolor(root, NodeColor::Black);
+        }
+        let left_height = black_height(&left);
+        let right_height = black_height(&right);
+        let taller_left = left_height >= right_height;
+        let (mut node, mut height, target) = if taller_left {
+            (left.clone(), left_height, right_height)
+        } else {
+            (right.clone(), right_height, left_height)
+        };
+        self.root = node.clone();
+
+        let mut parent = self.sentinel.clone();
+        while node.borrow().color == NodeColor::Red || height != target {
+            self.push_down(&node);
+            if node.borrow().color == NodeColor::Black {
+                height -= 1;
+            }
+            parent = node.clone();
+            let node_tmp = if taller_left {
+                node.borrow().right().clone()
+            } else {
+                node.borrow().left().clone()
+            };
+            node = node_tmp;
+        }
+
+        // `node` is the subtree replaced by `pivot`, the other child of
+        // `pivot` is the whole shorter subtree.
+        let (pivot_left, pivot_right) = if taller_left {
+            (node, right)
+        } else {
+            (left, node)
+        };
+        for child in [&pivot_left, &pivot_right] {
+            if !child.borrow().is_nil() {
+                child.borrow_mut().set_parent(pivot.clone());
+            }
+        }
+        {
+            let mut pivot_mut = pivot.borrow_mut();
+            pivot_mut.color = NodeColor::Red;
+            pivot_mut.set_parent(parent.clone());
+            pivot_mut.set_left_child(pivot_left);
+            pivot_mut.set_right_child(pivot_right);
+        }
+
+        if parent.borrow().is_nil() {
+            self.root = pivot.clone();
+        } else if taller_left {
+            parent.borrow_mut().set_right_child(pivot.clone());
+        } else {
+            parent.borrow_mut().set_left_child(pivot.clone());
+        }
+        let mut ancestor = pivot.clone();
+        while !ancestor.borrow().is_nil() {
+            self.update(&ancestor);
+            let ancestor_tmp = ancestor.borrow().parent().clone();
+            ancestor = ancestor_tmp;
+        }
+
+        self.insert_fix_up(pivot);
+        self.root.clone()
+    }
+}
+
+/// The number of black nodes on the leftmost path below `node`, `node`
+/// included and T.nil excluded.
+fn black_height<T>(node: &Rc<RefCell<Node<T>>>) -> usize {
+    let mut height = 0;
+    let mut node = node.clone();
+    while !node.borrow().is_nil() {
+        if node.borrow().color == NodeColor::Black {
+            height += 1;
+        }
+        let node_tmp = node.borrow().left().clone();
+        node = node_tmp;
+    }
+    height
+}
+
+/// Points the T.nil children of every node below `root`, and the parent of
+/// `root`, to `sentinel`. The nodes are stamped with fresh generations from
+/// `generation`, so that handles to them no longer resolve in the tree they
+/// came from.
+fn relink<T>(root: &Rc<RefCell<Node<T>>>, sentinel: &Rc<RefCell<Node<T>>>, generation: &mut u64) {
+    if root.borrow().is_nil() {
+        return;
+    }
+    root.borrow_mut().set_parent(sentinel.clone());
+    let mut stack = vec![root.clone()];
+    while let Some(node) = stack.pop() {
+        let mut node = node.borrow_mut();
+        let node = &mut *node;
+        *generation += 1;
+        node.generation = *generation;
+        for child in [node.left.as_mut().unwrap(), node.right.as_mut().unwrap()] {
+            if child.borrow().is_nil() {
+                *child = sentinel.clone();
+            } else {
+                stack.push(child.clone());
+            }
+        }
+    }
+}
@@ -0,0 +1,98 @@
+use crate::{node::NodeColor, tree::Shape};
+
+#[cfg(test)]
+mod observer_tests;
+
+/// Receives a notification for every step of an insertion or deletion in a
+/// [`Tree`], for measuring how much rebalancing a workload causes or for
+/// tracing the algorithms step by step.
+///
+/// Every method does nothing by default. The observer is a type parameter of
+/// the tree, so [`NoopObserver`], the default, costs nothing at runtime.
+///
+/// [`Tree`]: crate::tree::Tree
+pub trait Observer<T> {
+    /// Whether the tree passes a snapshot of itself to
+    /// [`Observer::on_snapshot`] after every step. Snapshots copy the whole
+    /// tree, so they are off by default.
+    const SNAPSHOTS: bool = false;
+
+    /// Called once `key` has been linked into the tree as a red leaf, before
+    /// `insert_fix_up` runs.
+    fn on_insert(&mut self, _key: &T) {}
+
+    /// Called once the node holding `key` has been spliced out, before
+    /// `delete_fix_up` runs.
+    fn on_delete(&mut self, _key: &T) {}
+
+    /// Called after a left rotation around the node holding `pivot`, which
+    /// moved down.
+    fn on_left_rotate(&mut self, _pivot: &T) {}
+
+    /// Called after a right rotation around the node holding `pivot`, which
+    /// moved down.
+    fn on_right_rotate(&mut self, _pivot: &T) {}
+
+    /// Called when case `case` of `insert_fix_up` fires. Cases 1 to 3 apply
+    /// when the parent of the new node is a left child, cases 4 to 6 are
+    /// their mirror images.
+    fn on_insert_case(&mut self, _case: u8) {}
+
+    /// Called when case `case` of `delete_fix_up` fires. Cases 1 to 4 apply
+    /// when the doubly black node is a left child, cases 5 to 8 are their
+    /// mirror images.
+    fn on_delete_case(&mut self, _case: u8) {}
+
+    /// Called after the fix-ups changed the color of the node holding `key`.
+    fn on_recolor(&mut self, _key: &T, _color: &NodeColor) {}
+
+    /// Receives the shape of the tree after every other notification if
+    /// [`Observer::SNAPSHOTS`] is set.
+    fn on_snapshot(&mut self, _shape: Shape<T>) {}
+}
+
+/// An [`Observer`] that ignores every notification.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct NoopObserver;
+
+impl<T> Observer<T> for NoopObserver {}
+
+/// An [`Observer`] that counts the rebalancing work.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RebalanceCounts {
+    pub left_rotations: u64,
+    pub right_rotations: u64,
+    /// How often each case of `insert_fix_up` fired, case 1 first.
+    pub insert_cases: [u64; 6],
+    /// How often each case of `delete_fix_up` fired, case 1 first.
+    pub delete_cases: [u64; 8],
+    pub recolorings: u64,
+}
+
+impl RebalanceCounts {
+    pub fn rotations(&self) -> u64 {
+        self.left_rotations + self.right_rotations
+    }
+}
+
+impl<T> Observer<T> for RebalanceCounts {
+    fn on_left_rotate(&mut self, _pivot: &T) {
+        self.left_rotations += 1;
+    }
+
+    fn on_right_rotate(&mut self, _pivot: &T) {
+        self.right_rotations += 1;
+    }
+
+    fn on_insert_case(&mut self, case: u8) {
+        self.insert_cases[usize::from(case) - 1] += 1;
+    }
+
+    fn on_delete_case(&mut self, case: u8) {
+        self.delete_cases[usize::from(case) - 1] += 1;
+    }
+
+    fn on_recolor(&mut self, _key: &T, _color: &NodeColor) {
+        self.recolorings += 1;
+    }
+}
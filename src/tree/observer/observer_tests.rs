@@ -0,0 +1,111 @@
+use crate::tree::{NoopObserver, RebalanceCounts, Tree};
+use proptest::prelude::*;
+use std::{format, mem};
+
+fn counted(keys: impl IntoIterator<Item = i32>) -> Tree<i32, RebalanceCounts> {
+    let mut tree = Tree::with_observer(RebalanceCounts::default());
+    for key in keys {
+        tree.insert(key);
+    }
+    tree
+}
+
+#[test]
+fn test_noop_observer_is_free() {
+    assert_eq!(mem::size_of::<NoopObserver>(), 0);
+    assert_eq!(
+        mem::size_of::<Tree<i32>>(),
+        mem::size_of::<Tree<i32, NoopObserver>>()
+    );
+}
+
+#[test]
+fn test_first_insert_recolors_root() {
+    let tree = counted([1]);
+
+    assert_eq!(
+        *tree.observer(),
+        RebalanceCounts {
+            recolorings: 1,
+            ..RebalanceCounts::default()
+        }
+    );
+}
+
+#[test]
+fn test_ascending_inserts_rotate_left() {
+    let tree = counted([1, 2, 3]);
+
+    let counts = tree.observer();
+    assert_eq!(counts.left_rotations, 1);
+    assert_eq!(counts.right_rotations, 0);
+    assert_eq!(counts.insert_cases, [0, 0, 0, 0, 0, 1]);
+    // The root once when inserting 1, then 2 and 1 in case 6.
+    assert_eq!(counts.recolorings, 3);
+}
+
+#[test]
+fn test_zig_zag_insert_rotates_twice() {
+    let tree = counted([3, 1, 2]);
+
+    let counts = tree.observer();
+    assert_eq!(counts.left_rotations, 1);
+    assert_eq!(counts.right_rotations, 1);
+    assert_eq!(counts.insert_cases, [0, 1, 1, 0, 0, 0]);
+}
+
+#[test]
+fn test_uncle_recoloring() {
+    let tree = counted([2, 1, 3, 4]);
+
+    let counts = tree.observer();
+    assert_eq!(counts.rotations(), 0);
+    assert_eq!(counts.insert_cases, [0, 0, 0, 1, 0, 0]);
+}
+
+#[test]
+fn test_delete_cases() {
+    let mut tree = counted(1..=10);
+    *tree.observer_mut() = RebalanceCounts::default();
+
+    tree.delete(1);
+
+    // Deleting the black leaf 1 recolors its sibling 3 red (case 2) and moves
+    // the extra black up to 2, whose sibling 6 has the red right child 8
+    // (case 4).
+    assert_eq!(
+        *tree.observer(),
+        RebalanceCounts {
+            left_rotations: 1,
+            delete_cases: [0, 1, 0, 1, 0, 0, 0, 0],
+            recolorings: 2,
+            ..RebalanceCounts::default()
+        }
+    );
+}
+
+proptest! {
+    #[test]
+    fn test_rotations_match_cases(keys in prop::collection::vec(any::<i16>(), 0..200)) {
+        let mut tree = Tree::with_observer(RebalanceCounts::default());
+        for key in &keys {
+            tree.insert(*key);
+        }
+        for key in keys.iter().step_by(2) {
+            tree.delete(*key);
+        }
+
+        // Every rotation belongs to exactly one case.
+        let counts = tree.observer();
+        let insert = counts.insert_cases;
+        let delete = counts.delete_cases;
+        prop_assert_eq!(
+            counts.left_rotations,
+            insert[1] + insert[5] + delete[0] + delete[3] + delete[6]
+        );
+        prop_assert_eq!(
+            counts.right_rotations,
+            insert[2] + insert[4] + delete[2] + delete[4] + delete[7]
+        );
+    }
+}
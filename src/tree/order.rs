@@ -0,0 +1,125 @@
+use crate::{
+    node::Node,
+    tree::{Observer, Tree},
+};
+use alloc::rc::Rc;
+use core::{
+    cell::RefCell,
+    fmt::Debug,
+    mem,
+    ops::{Bound, RangeBounds},
+};
+
+#[cfg(test)]
+mod order_tests;
+
+/// Access by in-order position, using the subtree sizes stored in the nodes.
+///
+/// The positional insertion below ignores how the keys order, so these are
+/// only used by [`Sequence`](crate::sequence::Sequence), which never searches
+/// by key.
+impl<T: PartialOrd + Clone + PartialEq + Debug + Default, O: Observer<T>> Tree<T, O> {
+    /// Returns the node at in-order position `index`, or `None` if `index`
+    /// is out of bounds.
+    pub(crate) fn node_at(&self, mut index: usize) -> Option<Rc<RefCell<Node<T>>>> {
+        let mut node = self.root.clone();
+        while !node.borrow().is_nil() {
+            let left_size = node.borrow().left().borrow().size;
+            let node_tmp = if index < left_size {
+                node.borrow().left().clone()
+            } else if index == left_size {
+                return Some(node);
+            } else {
+                index -= left_size + 1;
+                node.borrow().right().clone()
+            };
+            node = node_tmp;
+        }
+        None
+    }
+
+    /// Inserts `key` so that it ends up at in-order position `index`,
+    /// shifting the following elements back by one. `index` must not exceed
+    /// the length.
+    pub(crate) fn insert_at_index(&mut self, mut index: usize, key: T) {
+        let mut node = self.root.clone();
+        let mut parent = self.sentinel.clone();
+        let mut as_left = false;
+        while !node.borrow().is_nil() {
+            let left_size = node.borrow().left().borrow().size;
+            as_left = index <= left_size;
+            if !as_left {
+                index -= left_size + 1;
+            }
+            parent = node.clone();
+            let node_tmp = if as_left {
+                node.borrow().left().clone()
+            } else {
+                node.borrow().right().clone()
+            };
+            node = node_tmp;
+        }
+        self.insert_at(parent, as_left, key);
+        self.check_structure("insert_at_index");
+    }
+
+    /// Removes and returns the element at in-order position `index`, or
+    /// `None` if `index` is out of bounds.
+    pub(crate) fn remove_at_index(&mut self, index: usize) -> Option<T> {
+        let node = self.node_at(index)?;
+        self.delete_node(node.clone());
+        self.length -= 1;
+        self.check_structure("remove_at_index");
+        let key = mem::take(&mut node.borrow_mut().key);
+        Some(key)
+    }
+}
+
+/// Counting and selecting by key, which use the subtree sizes like the
+/// positional access above.
+impl<T: PartialOrd + Clone + PartialEq + Debug + Default, O: Observer<T>> Tree<T, O> {
+    /// Returns the number of keys within `range` in O(log n), without
+    /// visiting them.
+    pub fn count_range<R: RangeBounds<T>>(&self, range: R) -> usize {
+        let before_start = match range.start_bound() {
+            Bound::Included(key) => self.count_below(key, false),
+            Bound::Excluded(key) => self.count_below(key, true),
+            Bound::Unbounded => 0,
+        };
+        let before_end = match range.end_bound() {
+            Bound::Included(key) => self.count_below(key, true),
+            Bound::Excluded(key) => self.count_below(key, false),
+            Bound::Unbounded => self.length,
+        };
+        before_end.saturating_sub(before_start)
+    }
+
+    /// Returns the key at position `index` in ascending order, or `None` if
+    /// `index` is out of bounds, in O(log n).
+    pub fn select(&self, index: usize) -> Option<&T> {
+        let node = self.node_at(index)?;
+        Some(self.key_ref(&node))
+    }
+
+    /// Returns the number of keys less than `key`, or not greater than `key`
+    /// if `inclusive` is set.
+    fn count_below(&self, key: &T, inclusive: bool) -> usize {
+        let mut count = 0;
+        let mut node = self.root.clone();
+        while !node.borrow().is_nil() {
+            let below = if inclusive {
+                node.borrow().key <= *key
+            } else {
+                node.borrow().key < *key
+            };
+            let node_tmp = if below {
+                count += node.borrow().left().borrow().size + 1;
+                node.borrow().right().clone()
+            } else {
+                node.borrow().left().clone()
+            };
+            node = node_tmp;
+        }
+        count
+    }
+}
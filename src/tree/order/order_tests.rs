@@ -0,0 +1,80 @@
+use crate::tree::Tree;
+use core::ops::Bound;
+use proptest::prelude::*;
+use std::{collections::BTreeSet, format};
+
+#[test]
+fn test_count_range() {
+    let tree: Tree<i32> = (0..100).step_by(5).collect();
+
+    assert_eq!(tree.count_range(..), 20);
+    assert_eq!(tree.count_range(10..20), 2);
+    assert_eq!(tree.count_range(10..=20), 3);
+    assert_eq!(tree.count_range(11..20), 1);
+    assert_eq!(tree.count_range(..10), 2);
+    assert_eq!(tree.count_range(90..), 2);
+    assert_eq!(
+        tree.count_range((Bound::Excluded(10), Bound::Unbounded)),
+        17
+    );
+    assert_eq!(tree.count_range(200..300), 0);
+}
+
+#[test]
+fn test_count_empty_and_inverted_ranges() {
+    let tree: Tree<i32> = (0..10).collect();
+    assert_eq!(
+        tree.count_range((Bound::Excluded(5), Bound::Excluded(5))),
+        0
+    );
+    assert_eq!(
+        tree.count_range((Bound::Included(7), Bound::Excluded(3))),
+        0
+    );
+
+    assert_eq!(Tree::<i32>::new().count_range(..), 0);
+}
+
+#[test]
+fn test_count_range_after_removals() {
+    let mut tree: Tree<i32> = (0..100).collect();
+    for key in (0..100).step_by(3) {
+        tree.delete(key);
+    }
+
+    assert_eq!(tree.count_range(0..30), 20);
+    assert_eq!(tree.count_range(..), tree.len());
+}
+
+#[test]
+fn test_select() {
+    let mut tree: Tree<i32> = (0..100).step_by(2).rev().collect();
+    tree.delete(50);
+
+    assert_eq!(tree.select(0), Some(&0));
+    assert_eq!(tree.select(24), Some(&48));
+    assert_eq!(tree.select(25), Some(&52));
+    assert_eq!(tree.select(48), Some(&98));
+    assert_eq!(tree.select(49), None);
+}
+
+proptest! {
+    #[test]
+    fn test_count_range_matches_btree_set(
+        keys in prop::collection::vec(-50i32..50, 0..100),
+        start in -60i32..60,
+        end in -60i32..60,
+    ) {
+        let tree: Tree<i32> = keys.iter().copied().collect();
+        let model: BTreeSet<_> = keys.into_iter().collect();
+        if start <= end {
+            prop_assert_eq!(tree.count_range(start..end), model.range(start..end).count());
+            prop_assert_eq!(tree.count_range(start..=end), model.range(start..=end).count());
+        }
+        prop_assert_eq!(tree.count_range(start..), model.range(start..).count());
+        prop_assert_eq!(tree.count_range(..end), model.range(..end).count());
+        for (index, key) in model.iter().enumerate() {
+            prop_assert_eq!(tree.select(index), Some(key));
+        }
+    }
+}
@@ -0,0 +1,164 @@
+use crate::{
+    node::{Node, NodeColor},
+    tree::{Observer, Tree},
+};
+use alloc::{format, rc::Rc, string::String};
+use core::{
+    cell::RefCell,
+    fmt::{self, Debug, Display, Formatter},
+};
+
+#[cfg(test)]
+mod render_tests;
+
+const ANSI_RED: &str = "\x1b[31m";
+const ANSI_RESET: &str = "\x1b[0m";
+
+/// A sideways rendering of a [`Tree`] using box-drawing characters.
+///
+/// The root is printed in the first column, right subtrees above their parent
+/// and left subtrees below it, so the tree reads in sorted order from bottom to
+/// top. Every key is followed by an `(R)` or `(B)` color marker:
+///
+/// ```text
+///         ┌── 17 (R)
+///     ┌── 15 (B)
+/// ┌── 12 (R)
+/// │   └── 7 (B)
+/// 5 (B)
+/// └── 2 (B)
+/// ```
+///
+/// The output only depends on the shape of the tree, so it is suitable for
+/// snapshot tests and assertion messages.
+pub struct Render<'a, T> {
+    root: &'a Rc<RefCell<Node<T>>>,
+    ansi: bool,
+}
+
+impl<'a, T> Render<'a, T> {
+    /// Additionally wraps red nodes in ANSI escape codes for terminals.
+    pub fn ansi(mut self, enabled: bool) -> Self {
+        self.ansi = enabled;
+        self
+    }
+}
+
+impl<T: PartialOrd + Clone + PartialEq + Debug + Default, O: Observer<T>> Tree<T, O> {
+    /// Returns a [`Render`] of the tree that implements [`Display`].
+    pub fn render(&self) -> Render<'_, T> {
+        Render {
+            root: &self.root,
+            ansi: false,
+        }
+    }
+}
+
+impl<T: Display + Default> Display for Render<'_, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write_tree(f, self.root, self.ansi, &|key, f| Display::fmt(key, f))
+    }
+}
+
+impl<T: Display + Default, O> Display for Tree<T, O> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write_tree(f, &self.root, false, &|key, f| Display::fmt(key, f))
+    }
+}
+
+impl<T: Debug + Default, O> Debug for Tree<T, O> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write_tree(f, &self.root, false, &|key, f| Debug::fmt(key, f))
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Side {
+    Root,
+    Left,
+    Right,
+}
+
+type KeyFormatter<'a, T> = &'a dyn Fn(&T, &mut Formatter<'_>) -> fmt::Result;
+
+fn write_tree<T: Default>(
+    f: &mut Formatter<'_>,
+    root: &Rc<RefCell<Node<T>>>,
+    ansi: bool,
+    fmt_key: KeyFormatter<'_, T>,
+) -> fmt::Result {
+    if root.borrow().is_nil() {
+        return write!(f, "(empty)");
+    }
+    let mut first_line = true;
+    write_subtree(f, root, "", Side::Root, ansi, fmt_key, &mut first_line)
+}
+
+fn write_subtree<T: Default>(
+    f: &mut Formatter<'_>,
+    node: &Rc<RefCell<Node<T>>>,
+    prefix: &str,
+    side: Side,
+    ansi: bool,
+    fmt_key: KeyFormatter<'_, T>,
+    first_line: &mut bool,
+) -> fmt::Result {
+    let node = node.borrow();
+
+    // The children of the root hang off the first column. Deeper down, a
+    // vertical bar connects a node to its parent across the lines of the
+    // subtree printed in between them.
+    let child_prefix = |bar: bool| match side {
+        Side::Root => String::new(),
+        _ if bar => format!("{}│   ", prefix),
+        _ => format!("{}    ", prefix),
+    };
+
+    if !node.right().borrow().is_nil() {
+        let prefix = child_prefix(side == Side::Left);
+        write_subtree(
+            f,
+            node.right(),
+            &prefix,
+            Side::Right,
+            ansi,
+            fmt_key,
+            first_line,
+        )?;
+    }
+
+    if !*first_line {
+        writeln!(f)?;
+    }
+    *first_line = false;
+    let connector = match side {
+        Side::Root => "",
+        Side::Left => "└── ",
+        Side::Right => "┌── ",
+    };
+    write!(f, "{}{}", prefix, connector)?;
+    let is_red = node.color == NodeColor::Red;
+    if ansi && is_red {
+        write!(f, "{}", ANSI_RED)?;
+    }
+    fmt_key(&node.key, f)?;
+    write!(f, " ({})", if is_red { "R" } else { "B" })?;
+    if ansi && is_red {
+        write!(f, "{}", ANSI_RESET)?;
+    }
+
+    if !node.left().borrow().is_nil() {
+        let prefix = child_prefix(side == Side::Right);
+        write_subtree(
+            f,
+            node.left(),
+            &prefix,
+            Side::Left,
+            ansi,
+            fmt_key,
+            first_line,
+        )?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,84 @@
+use crate::tree::Tree;
+use std::{format, string::ToString};
+
+fn example_tree() -> Tree<i32> {
+    let mut tree = Tree::new();
+    for key in [5, 15, 2, 7, 12, 17] {
+        tree.insert(key);
+    }
+    tree
+}
+
+#[test]
+fn test_render_empty_tree() {
+    let tree: Tree<i32> = Tree::new();
+
+    assert_eq!(tree.render().to_string(), "(empty)");
+}
+
+#[test]
+fn test_render_single_node() {
+    let mut tree = Tree::new();
+    tree.insert(1);
+
+    assert_eq!(tree.render().to_string(), "1 (B)");
+}
+
+#[test]
+fn test_render_snapshot() {
+    let tree = example_tree();
+
+    let expected = [
+        "        ┌── 17 (R)",
+        "    ┌── 15 (B)",
+        "┌── 12 (R)",
+        "│   └── 7 (B)",
+        "5 (B)",
+        "└── 2 (B)",
+    ]
+    .join("\n");
+    assert_eq!(tree.render().to_string(), expected);
+    assert_eq!(tree.to_string(), expected);
+}
+
+#[test]
+fn test_render_deep_connectors() {
+    let mut tree = Tree::new();
+    for key in 1..=8 {
+        tree.insert(key);
+    }
+
+    let expected = [
+        "        ┌── 8 (R)",
+        "    ┌── 7 (B)",
+        "┌── 6 (R)",
+        "│   └── 5 (B)",
+        "4 (B)",
+        "│   ┌── 3 (B)",
+        "└── 2 (R)",
+        "    └── 1 (B)",
+    ]
+    .join("\n");
+    assert_eq!(tree.to_string(), expected);
+}
+
+#[test]
+fn test_render_ansi() {
+    let mut tree = Tree::new();
+    tree.insert(2);
+    tree.insert(1);
+
+    assert_eq!(
+        tree.render().ansi(true).to_string(),
+        "2 (B)\n└── \x1b[31m1 (R)\x1b[0m"
+    );
+}
+
+#[test]
+fn test_debug_uses_key_debug() {
+    let mut tree = Tree::new();
+    tree.insert("b".to_string());
+    tree.insert("a".to_string());
+
+    assert_eq!(format!("{:?}", tree), "\"b\" (B)\n└── \"a\" (R)");
+}
@@ -0,0 +1,71 @@
+use crate::{
+    node::Node,
+    tree::{Observer, Tree},
+};
+use alloc::rc::Rc;
+#[cfg(feature = "nightly")]
+use core::alloc::AllocError;
+use core::{cell::RefCell, fmt::Debug};
+
+#[cfg(all(test, feature = "nightly"))]
+mod reserve_tests;
+
+#[cfg(feature = "nightly")]
+impl<T: PartialOrd + Clone + PartialEq + Debug + Default, O: Observer<T>> Tree<T, O> {
+    /// Allocates nodes for at least `additional` more elements, so that the
+    /// next `additional` insertions do not allocate them.
+    ///
+    /// On failure the error is returned and the tree is left unchanged, with
+    /// the nodes allocated so far released again.
+    pub fn try_reserve(&mut self, additional: usize) -> Result<(), AllocError> {
+        let missing = additional.saturating_sub(self.spare.len());
+        if missing == 0 {
+            return Ok(());
+        }
+        self.spare.try_reserve(missing).map_err(|_| AllocError)?;
+
+        let reserved = self.spare.len();
+        for _ in 0..missing {
+            match Rc::try_new(RefCell::new(Node::new(T::default()))) {
+                Ok(node) => self.spare.push(node),
+                Err(error) => {
+                    self.spare.truncate(reserved);
+                    return Err(error);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Inserts `key` like [`Tree::insert`] after reserving its node with
+    /// [`Tree::try_reserve`], returning the error of the reservation instead.
+    /// The tree is left unchanged on failure. Reserves a node even if an
+    /// equal key is already present.
+    ///
+    /// The node is the only allocation of an insertion into a plain tree,
+    /// with no augmentation, the default observer and the `paranoid` feature
+    /// off. Augmented trees collect the path to the root, observers taking
+    /// snapshots copy the tree, and the `paranoid` checks allocate as well,
+    /// all of which still abort when memory runs out.
+    pub fn try_insert(&mut self, key: T) -> Result<bool, AllocError> {
+        self.try_reserve(1)?;
+        Ok(self.insert(key))
+    }
+}
+
+impl<T: PartialOrd + Clone + PartialEq + Debug + Default, O: Observer<T>> Tree<T, O> {
+    /// Returns a node holding `key` with no links and a fresh generation,
+    /// taken from the spare nodes if there are any.
+    pub(super) fn new_node(&mut self, key: T) -> Rc<RefCell<Node<T>>> {
+        let node = match self.spare.pop() {
+            Some(node) => {
+                node.borrow_mut().key = key;
+                node
+            }
+            None => Rc::new(RefCell::new(Node::new(key))),
+        };
+        self.generation += 1;
+        node.borrow_mut().generation = self.generation;
+        node
+    }
+}
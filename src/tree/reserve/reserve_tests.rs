@@ -0,0 +1,135 @@
+use crate::tree::{tree_tests::assert_red_black_tree_properties, Tree};
+use core::{
+    alloc::{GlobalAlloc, Layout},
+    cell::Cell,
+    ptr,
+};
+use std::{alloc::System, rc::Rc, thread_local, vec::Vec};
+
+/// Passes allocations on to the system allocator until the budget of the
+/// current thread is used up, and fails them after that.
+struct FailingAllocator;
+
+thread_local! {
+    /// How many more allocations the current thread may make, or `None` for
+    /// no limit.
+    static BUDGET: Cell<Option<usize>> = const { Cell::new(None) };
+}
+
+unsafe impl GlobalAlloc for FailingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let allowed = BUDGET.with(|budget| match budget.get() {
+            None => true,
+            Some(0) => false,
+            Some(left) => {
+                budget.set(Some(left - 1));
+                true
+            }
+        });
+        if allowed {
+            System.alloc(layout)
+        } else {
+            ptr::null_mut()
+        }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: FailingAllocator = FailingAllocator;
+
+/// Runs `f` with the current thread allowed `budget` allocations.
+fn with_budget<R>(budget: usize, f: impl FnOnce() -> R) -> R {
+    BUDGET.with(|limit| limit.set(Some(budget)));
+    let result = f();
+    BUDGET.with(|limit| limit.set(None));
+    result
+}
+
+#[test]
+fn test_try_reserve_allocates_spare_nodes() {
+    let mut tree: Tree<i32> = Tree::new();
+
+    tree.try_reserve(10).unwrap();
+
+    assert_eq!(tree.spare.len(), 10);
+    assert!(tree.is_empty());
+    tree.try_reserve(4).unwrap();
+    assert_eq!(tree.spare.len(), 10);
+}
+
+#[test]
+fn test_insertions_use_spare_nodes() {
+    let mut tree = Tree::new();
+    tree.try_reserve(3).unwrap();
+    let spare: Vec<_> = tree.spare.iter().map(Rc::downgrade).collect();
+
+    for key in [2, 1, 3] {
+        tree.insert(key);
+    }
+
+    assert!(tree.spare.is_empty());
+    assert!(spare.iter().all(|node| node.upgrade().is_some()));
+    assert_eq!(tree.len(), 3);
+    assert_eq!(tree.first(), Some(&1));
+    assert_red_black_tree_properties(&tree);
+}
+
+#[test]
+fn test_try_insert() {
+    let mut tree = Tree::new();
+    for key in 0..100 {
+        tree.try_insert(key).unwrap();
+    }
+
+    assert_eq!(tree.len(), 100);
+    assert!(tree.spare.is_empty());
+    assert_red_black_tree_properties(&tree);
+}
+
+#[test]
+fn test_failed_reserve_leaves_tree_unchanged() {
+    let mut tree = Tree::new();
+    tree.insert(1);
+    tree.try_reserve(2).unwrap();
+
+    // Enough to grow the list of spare nodes and allocate two more nodes.
+    let result = with_budget(3, || tree.try_reserve(10));
+
+    assert!(result.is_err());
+    assert_eq!(tree.len(), 1);
+    assert_eq!(tree.spare.len(), 2);
+    assert_eq!(tree.first(), Some(&1));
+}
+
+#[test]
+fn test_failed_try_insert_leaves_tree_unchanged() {
+    let mut tree = Tree::new();
+    for key in 1..=3 {
+        tree.insert(key);
+    }
+
+    let result = with_budget(0, || tree.try_insert(4));
+
+    assert!(result.is_err());
+    assert_eq!(tree.len(), 3);
+    assert!(!tree.contains_key(4));
+    assert_red_black_tree_properties(&tree);
+    assert_eq!(tree.try_insert(4), Ok(true));
+}
+
+#[cfg(not(feature = "paranoid"))]
+#[test]
+fn test_insertions_after_reserve_do_not_allocate() {
+    let mut tree = Tree::new();
+    tree.try_reserve(100).unwrap();
+
+    let inserted = with_budget(0, || (0..100).all(|key| tree.insert(key)));
+
+    assert!(inserted);
+    assert_eq!(tree.len(), 100);
+    assert_red_black_tree_properties(&tree);
+}
@@ -0,0 +1,130 @@
+use crate::{
+    node::Node,
+    tree::{Observer, Tree},
+};
+use alloc::rc::Rc;
+use core::{cell::RefCell, cmp::Ordering, fmt::Debug};
+
+/// Where a search for a key ended.
+pub(crate) enum Position<T> {
+    /// The node holding the key.
+    Found(Rc<RefCell<Node<T>>>),
+    /// The leaf position the key belongs at: a child of `parent`, which is
+    /// T.nil if the tree is empty.
+    Vacant {
+        parent: Rc<RefCell<Node<T>>>,
+        as_left: bool,
+    },
+}
+
+/// How `key` orders relative to `other`, decided with `==` and `<` like the
+/// descent of [`Tree::insert`], so that incomparable keys order as greater.
+pub(crate) fn compare<T: PartialOrd>(key: &T, other: &T) -> Ordering {
+    if key == other {
+        Ordering::Equal
+    } else if key < other {
+        Ordering::Less
+    } else {
+        Ordering::Greater
+    }
+}
+
+/// Low-level access for the wrappers around [`Tree`] elsewhere in the crate,
+/// which store more than the ordered key in `T`.
+impl<T: PartialOrd + Clone + PartialEq + Debug + Default, O: Observer<T>> Tree<T, O> {
+    /// Descends from the root, comparing the searched key against each key
+    /// with `compare`, which returns how the searched key orders relative to
+    /// the given one.
+    pub(crate) fn position_by<F>(&self, compare: F) -> Position<T>
+    where
+        F: FnMut(&T) -> Ordering,
+    {
+        self.position_from(self.root.clone(), compare)
+    }
+
+    /// Like [`Tree::position_by`], but descends from `node` instead of the
+    /// root. The searched key must belong in the subtree of `node`.
+    pub(crate) fn position_from<F>(
+        &self,
+        mut node: Rc<RefCell<Node<T>>>,
+        mut compare: F,
+    ) -> Position<T>
+    where
+        F: FnMut(&T) -> Ordering,
+    {
+        let mut parent = self.sentinel.clone();
+        let mut as_left = false;
+        while !node.borrow().is_nil() {
+            let ordering = compare(&node.borrow().key);
+            as_left = match ordering {
+                Ordering::Equal => return Position::Found(node),
+                Ordering::Less => true,
+                Ordering::Greater => false,
+            };
+            parent = node.clone();
+            let node_tmp = if as_left {
+                node.borrow().left().clone()
+            } else {
+                node.borrow().right().clone()
+            };
+            node = node_tmp;
+        }
+        Position::Vacant { parent, as_left }
+    }
+
+    /// Returns the node holding the smallest key, or `None` if the tree is
+    /// empty.
+    pub(crate) fn first_node(&self) -> Option<Rc<RefCell<Node<T>>>> {
+        if self.root.borrow().is_nil() {
+            return None;
+        }
+        self.minimum_node(self.root.clone())
+    }
+
+    /// Returns the node holding the largest key, or `None` if the tree is
+    /// empty.
+    pub(crate) fn last_node(&self) -> Option<Rc<RefCell<Node<T>>>> {
+        if self.root.borrow().is_nil() {
+            return None;
+        }
+        self.maximum_node(self.root.clone())
+    }
+
+    /// Inserts `key` at a position returned by [`Tree::position_by`], without
+    /// searching again. The tree must not have changed since.
+    pub(crate) fn insert_vacant(
+        &mut self,
+        parent: Rc<RefCell<Node<T>>>,
+        as_left: bool,
+        key: T,
+    ) -> Rc<RefCell<Node<T>>> {
+        let z = self.insert_at(parent, as_left, key);
+        self.check_invariants("insert_vacant", Some(&z.borrow().key));
+        z
+    }
+
+    /// Removes `node` from the tree and returns its key.
+    pub(crate) fn remove_node(&mut self, node: Rc<RefCell<Node<T>>>) -> T {
+        self.delete_node(node.clone());
+        self.length -= 1;
+        let key = node.borrow().key.clone();
+        self.check_invariants("remove_node", Some(&key));
+        key
+    }
+
+    /// Returns the key of `node` mutably borrowed for as long as the tree is,
+    /// without holding a `RefMut` guard. `node` must belong to this tree, and
+    /// the caller must not change how the key orders.
+    ///
+    /// Relies on the same rule as [`Tree::key_ref`].
+    pub(crate) fn key_mut<'a>(&'a mut self, node: &Rc<RefCell<Node<T>>>) -> &'a mut T {
+        debug_assert!(node.try_borrow_mut().is_ok(), "Node is borrowed");
+        // SAFETY: the tree holds a strong reference to `node`, and removing
+        // the node takes `&mut self`, so it lives for `'a`. By the rule of
+        // `key_ref` no `Ref` or `RefMut` to it exists now. Keys returned by
+        // `key_ref` borrow the tree shared and so cannot outlive this call,
+        // and while `'a` keeps the tree exclusively borrowed nothing else can
+        // reach the node.
+        unsafe { &mut (*node.as_ptr()).key }
+    }
+}
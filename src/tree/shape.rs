@@ -0,0 +1,421 @@
+use crate::{
+    node::{Node, NodeColor},
+    tree::{NoopObserver, Observer, Tree, ValidationError},
+};
+use alloc::{boxed::Box, format, rc::Rc, string::String, vec};
+use core::{
+    cell::RefCell,
+    fmt::{self, Debug, Display, Formatter, Write},
+    str::FromStr,
+};
+
+#[cfg(test)]
+mod shape_tests;
+
+/// No red-black tree is higher than this: one with n nodes has height at most
+/// 2 log2(n + 1), and n fits in a `usize`.
+const MAX_HEIGHT: usize = 2 * usize::BITS as usize;
+
+/// An owned copy of the shape, keys and colors of a [`Tree`], independent of
+/// the tree it was taken from. [`Tree::to_shape`] and [`Tree::from_shape`]
+/// convert between the two exactly, so a shape can reproduce a tree node for
+/// node:
+///
+/// ```
+/// use atlas_rb_tree::tree::{NodeColor, Shape, Tree};
+///
+/// let leaf = |key| Shape::Node {
+///     key,
+///     color: NodeColor::Red,
+///     left: Box::new(Shape::Nil),
+///     right: Box::new(Shape::Nil),
+/// };
+/// let shape = Shape::Node {
+///     key: 2,
+///     color: NodeColor::Black,
+///     left: Box::new(leaf(1)),
+///     right: Box::new(leaf(3)),
+/// };
+///
+/// let tree = Tree::from_shape(shape.clone()).unwrap();
+/// assert_eq!(tree.to_shape(), shape);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Shape<T> {
+    /// An empty subtree, T.nil.
+    Nil,
+    Node {
+        key: T,
+        color: NodeColor,
+        left: Box<Shape<T>>,
+        right: Box<Shape<T>>,
+    },
+}
+
+impl<T> Shape<T> {
+    pub fn is_nil(&self) -> bool {
+        matches!(self, Shape::Nil)
+    }
+
+    /// Whether some path down from here has more than `limit` nodes, found
+    /// without recursing.
+    fn deeper_than(&self, limit: usize) -> bool {
+        let mut stack = vec![(self, 0)];
+        while let Some((shape, depth)) = stack.pop() {
+            if let Shape::Node { left, right, .. } = shape {
+                if depth == limit {
+                    return true;
+                }
+                stack.push((left, depth + 1));
+                stack.push((right, depth + 1));
+            }
+        }
+        false
+    }
+
+    /// Drops the shape one node at a time, where dropping it as a whole would
+    /// recurse once per level.
+    fn dismantle(self) {
+        let mut stack = vec![self];
+        while let Some(shape) = stack.pop() {
+            if let Shape::Node { left, right, .. } = shape {
+                stack.push(*left);
+                stack.push(*right);
+            }
+        }
+    }
+}
+
+impl<T: Display> Shape<T> {
+    /// Renders the shape as nested JSON objects with `key`, `color`, `left`
+    /// and `right`, with `null` for T.nil. Keys are strings holding their
+    /// `Display` representation:
+    ///
+    /// ```json
+    /// {"key": "1", "color": "black", "left": null, "right": null}
+    /// ```
+    ///
+    /// [`Shape::from_json`] reads it back.
+    pub fn to_json(&self) -> String {
+        let mut out = String::new();
+        self.fmt_json(&mut out, |out, key| write_json_string(out, key))
+            .expect("Writing to a String cannot fail");
+        out
+    }
+}
+
+impl<T> Shape<T> {
+    /// Writes the shape as JSON, with `write_key` writing each key as a JSON
+    /// value.
+    pub(super) fn fmt_json<W: Write>(
+        &self,
+        out: &mut W,
+        write_key: fn(&mut W, &T) -> fmt::Result,
+    ) -> fmt::Result {
+        match self {
+            Shape::Nil => write!(out, "null"),
+            Shape::Node {
+                key,
+                color,
+                left,
+                right,
+            } => {
+                write!(out, "{{\"key\": ")?;
+                write_key(out, key)?;
+                write!(out, ", \"color\": \"{}\", \"left\": ", color_name(color))?;
+                left.fmt_json(out, write_key)?;
+                write!(out, ", \"right\": ")?;
+                right.fmt_json(out, write_key)?;
+                write!(out, "}}")
+            }
+        }
+    }
+}
+
+impl<T: FromStr> Shape<T> {
+    /// Parses the JSON written by [`Shape::to_json`]. Each key string is
+    /// parsed with `FromStr`, so shapes round-trip through JSON for keys whose
+    /// `FromStr` reads back what their `Display` writes, such as integers,
+    /// `bool` and `String`:
+    ///
+    /// ```
+    /// use atlas_rb_tree::tree::{Shape, Tree};
+    ///
+    /// let mut tree = Tree::new();
+    /// for key in 1..=3 {
+    ///     tree.insert(key);
+    /// }
+    /// let json = tree.to_shape().to_json();
+    ///
+    /// assert_eq!(Shape::from_json(&json), Ok(tree.to_shape()));
+    /// ```
+    ///
+    /// Nesting deeper than any red-black tree can be is rejected.
+    pub fn from_json(json: &str) -> Result<Shape<T>, ParseShapeError> {
+        let mut parser = JsonParser {
+            input: json,
+            position: 0,
+        };
+        let shape = parser.shape(0)?;
+        parser.skip_whitespace();
+        if parser.position != json.len() {
+            return Err(parser.error());
+        }
+        Ok(shape)
+    }
+}
+
+/// Returned by [`Shape::from_json`] for input that is not a shape as written
+/// by [`Shape::to_json`], or whose keys do not parse.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseShapeError {
+    offset: usize,
+}
+
+impl ParseShapeError {
+    /// The byte offset in the input where parsing failed.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+}
+
+impl Display for ParseShapeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid shape at byte {}", self.offset)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseShapeError {}
+
+/// A recursive descent parser for the JSON of [`Shape::to_json`]. It recurses
+/// once per level, so it stops at [`MAX_HEIGHT`].
+struct JsonParser<'a> {
+    input: &'a str,
+    position: usize,
+}
+
+impl JsonParser<'_> {
+    fn error(&self) -> ParseShapeError {
+        ParseShapeError {
+            offset: self.position,
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = &self.input[self.position..];
+        self.position += rest.len() - rest.trim_start_matches([' ', '\t', '\n', '\r']).len();
+    }
+
+    /// Consumes `token` after any whitespace if it comes next.
+    fn eat(&mut self, token: &str) -> bool {
+        self.skip_whitespace();
+        let found = self.input[self.position..].starts_with(token);
+        if found {
+            self.position += token.len();
+        }
+        found
+    }
+
+    fn expect(&mut self, token: &str) -> Result<(), ParseShapeError> {
+        if self.eat(token) {
+            Ok(())
+        } else {
+            Err(self.error())
+        }
+    }
+
+    /// Parses a node at `depth` levels below the root, or `null`.
+    fn shape<T: FromStr>(&mut self, depth: usize) -> Result<Shape<T>, ParseShapeError> {
+        if self.eat("null") {
+            return Ok(Shape::Nil);
+        }
+        if depth == MAX_HEIGHT {
+            return Err(self.error());
+        }
+        self.expect("{")?;
+        let (mut key, mut color, mut left, mut right) = (None, None, None, None);
+        loop {
+            self.skip_whitespace();
+            let field_start = self.position;
+            let field = self.string()?;
+            self.expect(":")?;
+            self.skip_whitespace();
+            let value_start = self.position;
+            match field.as_str() {
+                "key" if key.is_none() => {
+                    let parsed = self.string()?.parse().map_err(|_| ParseShapeError {
+                        offset: value_start,
+                    })?;
+                    key = Some(parsed);
+                }
+                "color" if color.is_none() => {
+                    color = Some(match self.string()?.as_str() {
+                        "red" => NodeColor::Red,
+                        "black" => NodeColor::Black,
+                        _ => {
+                            return Err(ParseShapeError {
+                                offset: value_start,
+                            })
+                        }
+                    });
+                }
+                "left" if left.is_none() => left = Some(self.shape(depth + 1)?),
+                "right" if right.is_none() => right = Some(self.shape(depth + 1)?),
+                _ => {
+                    return Err(ParseShapeError {
+                        offset: field_start,
+                    })
+                }
+            }
+            if !self.eat(",") {
+                break;
+            }
+        }
+        self.skip_whitespace();
+        let (Some(key), Some(color), Some(left), Some(right)) = (key, color, left, right) else {
+            return Err(self.error());
+        };
+        self.expect("}")?;
+        Ok(Shape::Node {
+            key,
+            color,
+            left: Box::new(left),
+            right: Box::new(right),
+        })
+    }
+
+    /// Parses a string literal, undoing the escapes of `write_json_string` and
+    /// the others JSON allows.
+    fn string(&mut self) -> Result<String, ParseShapeError> {
+        self.expect("\"")?;
+        let mut out = String::new();
+        let mut chars = self.input[self.position..].chars();
+        loop {
+            let offset = self.input.len() - self.position - chars.as_str().len();
+            let c = match chars.next() {
+                Some('"') => {
+                    self.position += offset + 1;
+                    return Ok(out);
+                }
+                Some('\\') => match chars.next() {
+                    Some('"') => Some('"'),
+                    Some('\\') => Some('\\'),
+                    Some('/') => Some('/'),
+                    Some('b') => Some('\u{8}'),
+                    Some('f') => Some('\u{c}'),
+                    Some('n') => Some('\n'),
+                    Some('r') => Some('\r'),
+                    Some('t') => Some('\t'),
+                    Some('u') => {
+                        let rest = chars.as_str();
+                        let digits = rest
+                            .get(..4)
+                            .filter(|digits| digits.chars().all(|digit| digit.is_ascii_hexdigit()));
+                        chars = rest.get(4..).unwrap_or_default().chars();
+                        digits
+                            .and_then(|digits| u32::from_str_radix(digits, 16).ok())
+                            .and_then(char::from_u32)
+                    }
+                    _ => None,
+                },
+                Some(c) if !c.is_control() => Some(c),
+                _ => None,
+            };
+            match c {
+                Some(c) => out.push(c),
+                None => {
+                    self.position += offset;
+                    return Err(self.error());
+                }
+            }
+        }
+    }
+}
+
+impl<T: PartialOrd + Clone + PartialEq + Debug + Default> Tree<T> {
+    /// Builds a tree with exactly the given shape, keys and colors, as
+    /// returned by [`Tree::to_shape`]. Fails if the shape breaks the
+    /// red-black properties or the keys are out of order.
+    pub fn from_shape(shape: Shape<T>) -> Result<Tree<T>, ValidationError> {
+        if shape.deeper_than(MAX_HEIGHT) {
+            shape.dismantle();
+            return Err(ValidationError::TooDeep);
+        }
+        let mut tree = Tree::with_observer(NoopObserver);
+        let sentinel = tree.sentinel.clone();
+        tree.root = tree.build(shape, &sentinel);
+        tree.validate()?;
+        Ok(tree)
+    }
+}
+
+impl<T: PartialOrd + Clone + PartialEq + Debug + Default, O: Observer<T>> Tree<T, O> {
+    /// Copies the shape, keys and colors of the tree.
+    pub fn to_shape(&self) -> Shape<T> {
+        shape_of(&self.root)
+    }
+
+    /// Links the nodes of `shape` below `parent` and returns the root of the
+    /// new subtree. Recurses once per level, so [`Tree::from_shape`] bounds
+    /// the height of `shape` first.
+    fn build(&mut self, shape: Shape<T>, parent: &Rc<RefCell<Node<T>>>) -> Rc<RefCell<Node<T>>> {
+        let Shape::Node {
+            key,
+            color,
+            left,
+            right,
+        } = shape
+        else {
+            return self.sentinel.clone();
+        };
+        let node = self.new_node(key);
+        let left = self.build(*left, &node);
+        let right = self.build(*right, &node);
+        let mut node_mut = node.borrow_mut();
+        node_mut.color = color;
+        node_mut.set_parent(parent.clone());
+        node_mut.set_left_child(left);
+        node_mut.set_right_child(right);
+        drop(node_mut);
+        self.update(&node);
+        self.length += 1;
+        node
+    }
+}
+
+/// Recurses once per level, which the red-black properties of the tree bound
+/// by [`MAX_HEIGHT`].
+fn shape_of<T: Clone>(node: &Rc<RefCell<Node<T>>>) -> Shape<T> {
+    let node = node.borrow();
+    if node.is_nil() {
+        return Shape::Nil;
+    }
+    Shape::Node {
+        key: node.key.clone(),
+        color: node.color.clone(),
+        left: Box::new(shape_of(node.left())),
+        right: Box::new(shape_of(node.right())),
+    }
+}
+
+/// Writes `text` as a JSON string.
+pub(super) fn write_json_string<W: Write>(out: &mut W, text: &dyn Display) -> fmt::Result {
+    write!(out, "\"")?;
+    for c in format!("{}", text).chars() {
+        match c {
+            '"' => write!(out, "\\\"")?,
+            '\\' => write!(out, "\\\\")?,
+            c if c.is_control() => write!(out, "\\u{:04x}", c as u32)?,
+            c => write!(out, "{}", c)?,
+        }
+    }
+    write!(out, "\"")
+}
+
+pub(super) fn color_name(color: &NodeColor) -> &'static str {
+    match color {
+        NodeColor::Red => "red",
+        NodeColor::Black => "black",
+    }
+}
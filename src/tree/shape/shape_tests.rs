@@ -0,0 +1,283 @@
+use crate::tree::{NodeColor, ParseShapeError, Shape, Tree, ValidationError};
+use std::{boxed::Box, string::String, vec::Vec};
+
+fn leaf(key: i32, color: NodeColor) -> Shape<i32> {
+    Shape::Node {
+        key,
+        color,
+        left: Box::new(Shape::Nil),
+        right: Box::new(Shape::Nil),
+    }
+}
+
+#[test]
+fn test_to_shape_of_empty_tree() {
+    let tree: Tree<i32> = Tree::new();
+
+    assert!(tree.to_shape().is_nil());
+}
+
+#[test]
+fn test_to_shape() {
+    let mut tree = Tree::new();
+    for key in [2, 1, 3] {
+        tree.insert(key);
+    }
+
+    assert_eq!(
+        tree.to_shape(),
+        Shape::Node {
+            key: 2,
+            color: NodeColor::Black,
+            left: Box::new(leaf(1, NodeColor::Red)),
+            right: Box::new(leaf(3, NodeColor::Red)),
+        }
+    );
+}
+
+#[test]
+fn test_from_shape_round_trips() {
+    let mut tree = Tree::new();
+    for key in 0..50 {
+        tree.insert((key * 17) % 50);
+    }
+
+    let copy = Tree::from_shape(tree.to_shape()).unwrap();
+
+    assert_eq!(copy.to_shape(), tree.to_shape());
+    assert_eq!(copy.len(), 50);
+    assert_eq!(copy.validate(), Ok(()));
+}
+
+#[test]
+fn test_from_shape_of_nil_is_empty() {
+    let tree: Tree<i32> = Tree::from_shape(Shape::Nil).unwrap();
+
+    assert!(tree.is_empty());
+}
+
+#[test]
+fn test_from_shape_keeps_working_after_mutation() {
+    let shape = Shape::Node {
+        key: 2,
+        color: NodeColor::Black,
+        left: Box::new(leaf(1, NodeColor::Black)),
+        right: Box::new(leaf(3, NodeColor::Black)),
+    };
+    let mut tree = Tree::from_shape(shape).unwrap();
+
+    tree.insert(4);
+    tree.delete(1);
+
+    assert_eq!(tree.validate(), Ok(()));
+    assert_eq!(tree.iter().copied().collect::<Vec<_>>(), [2, 3, 4]);
+}
+
+#[test]
+fn test_from_shape_rejects_red_root() {
+    let result = Tree::from_shape(leaf(1, NodeColor::Red));
+
+    assert_eq!(result.err(), Some(ValidationError::RedRoot));
+}
+
+#[test]
+fn test_from_shape_rejects_red_child_of_red() {
+    let shape = Shape::Node {
+        key: 2,
+        color: NodeColor::Black,
+        left: Box::new(Shape::Node {
+            key: 1,
+            color: NodeColor::Red,
+            left: Box::new(leaf(0, NodeColor::Red)),
+            right: Box::new(Shape::Nil),
+        }),
+        right: Box::new(Shape::Node {
+            key: 4,
+            color: NodeColor::Red,
+            left: Box::new(leaf(3, NodeColor::Red)),
+            right: Box::new(Shape::Nil),
+        }),
+    };
+
+    assert_eq!(
+        Tree::from_shape(shape).err(),
+        Some(ValidationError::RedChildOfRed)
+    );
+}
+
+#[test]
+fn test_from_shape_rejects_black_height_mismatch() {
+    let shape = Shape::Node {
+        key: 2,
+        color: NodeColor::Black,
+        left: Box::new(leaf(1, NodeColor::Black)),
+        right: Box::new(Shape::Nil),
+    };
+
+    assert_eq!(
+        Tree::from_shape(shape).err(),
+        Some(ValidationError::BlackHeightMismatch)
+    );
+}
+
+#[test]
+fn test_from_shape_rejects_keys_out_of_order() {
+    let shape = Shape::Node {
+        key: 2,
+        color: NodeColor::Black,
+        left: Box::new(leaf(3, NodeColor::Red)),
+        right: Box::new(leaf(1, NodeColor::Red)),
+    };
+
+    assert_eq!(
+        Tree::from_shape(shape).err(),
+        Some(ValidationError::OutOfOrder)
+    );
+}
+
+#[test]
+fn test_to_json() {
+    let shape = Shape::Node {
+        key: 2,
+        color: NodeColor::Black,
+        left: Box::new(leaf(1, NodeColor::Red)),
+        right: Box::new(Shape::Nil),
+    };
+
+    assert_eq!(
+        shape.to_json(),
+        r#"{"key": "2", "color": "black", "left": {"key": "1", "color": "red", "left": null, "right": null}, "right": null}"#
+    );
+}
+
+#[test]
+fn test_from_shape_rejects_equal_keys() {
+    let shape = Shape::Node {
+        key: 2,
+        color: NodeColor::Black,
+        left: Box::new(leaf(2, NodeColor::Red)),
+        right: Box::new(Shape::Nil),
+    };
+
+    assert_eq!(
+        Tree::from_shape(shape).err(),
+        Some(ValidationError::OutOfOrder)
+    );
+}
+
+#[test]
+fn test_from_shape_rejects_shape_too_deep_for_any_tree() {
+    let mut shape = Shape::Nil;
+    for key in 0..1000 {
+        shape = Shape::Node {
+            key,
+            color: NodeColor::Black,
+            left: Box::new(Shape::Nil),
+            right: Box::new(shape),
+        };
+    }
+
+    assert_eq!(
+        Tree::from_shape(shape).err(),
+        Some(ValidationError::TooDeep)
+    );
+}
+
+#[test]
+fn test_json_round_trips() {
+    let mut tree = Tree::new();
+    for key in 0..50 {
+        tree.insert((key * 17) % 50 - 25);
+    }
+    let shape = tree.to_shape();
+
+    let parsed = Shape::from_json(&shape.to_json()).unwrap();
+
+    assert_eq!(parsed, shape);
+    assert_eq!(Tree::from_shape(parsed).unwrap().to_shape(), shape);
+}
+
+#[test]
+fn test_json_round_trips_string_keys() {
+    let mut tree = Tree::new();
+    for key in [
+        "plain",
+        "say \"hi\"",
+        "back\\slash",
+        "tab\tnew\nline",
+        "ünïcødé 🌳",
+        "",
+    ] {
+        tree.insert(String::from(key));
+    }
+    let shape = tree.to_shape();
+
+    let json = shape.to_json();
+
+    assert!(json.contains(r#""key": "say \"hi\"""#));
+    assert_eq!(Shape::from_json(&json), Ok(shape));
+}
+
+#[test]
+fn test_from_json_accepts_whitespace_and_any_field_order() {
+    let json = r#" { "right" : null , "color" : "black" ,
+        "left" : null , "key" : "\u0037" } "#;
+
+    assert_eq!(Shape::from_json(json), Ok(leaf(7, NodeColor::Black)));
+    assert_eq!(Shape::<i32>::from_json("null"), Ok(Shape::Nil));
+}
+
+#[test]
+fn test_from_json_rejects_malformed_input() {
+    let node = r#"{"key": "1", "color": "black", "left": null, "right": null}"#;
+    let cases = [
+        ("", 0),
+        (
+            r#"{"key": "x", "color": "black", "left": null, "right": null}"#,
+            8,
+        ),
+        (
+            r#"{"key": "1", "color": "blue", "left": null, "right": null}"#,
+            22,
+        ),
+        (
+            r#"{"key": "1", "key": "2", "left": null, "right": null}"#,
+            13,
+        ),
+        (r#"{"key": "1", "color": "black", "left": null}"#, 43),
+        (
+            r#"{"key": "1", "color": "black", "left": null, "right": null"#,
+            58,
+        ),
+        (
+            r#"{"key": "1\q", "color": "black", "left": null, "right": null}"#,
+            10,
+        ),
+    ];
+
+    for (json, offset) in cases {
+        assert_eq!(
+            Shape::<i32>::from_json(json).map_err(|error| error.offset()),
+            Err(offset),
+            "{json}"
+        );
+    }
+    assert_eq!(
+        Shape::<i32>::from_json(&(String::from(node) + " null")),
+        Err(ParseShapeError {
+            offset: node.len() + 1
+        })
+    );
+}
+
+#[test]
+fn test_from_json_rejects_nesting_too_deep_for_any_tree() {
+    let mut json = String::new();
+    for _ in 0..1000 {
+        json += r#"{"key": "1", "color": "black", "left": null, "right": "#;
+    }
+    json += "null";
+    json += &"}".repeat(1000);
+
+    assert!(Shape::<i32>::from_json(&json).is_err());
+}
@@ -0,0 +1,269 @@
+use crate::{
+    node::{Node, NodeColor},
+    tree::{Observer, Tree},
+};
+use alloc::{format, rc::Rc, vec};
+use core::{
+    cell::RefCell,
+    fmt::{self, Debug, Display, Formatter},
+    mem,
+};
+
+#[cfg(test)]
+mod stats_tests;
+
+/// Shape and memory statistics of a [`Tree`], returned by [`Tree::stats`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct TreeStats {
+    /// The number of elements.
+    pub len: usize,
+    /// The number of nodes on the longest path from the root to a leaf.
+    pub height: usize,
+    /// The number of black nodes on a path from the root to a leaf, the root
+    /// included and T.nil excluded. Every path has the same black height in a
+    /// valid tree; otherwise this is the black height of the leftmost path.
+    pub black_height: usize,
+    pub red_nodes: usize,
+    pub black_nodes: usize,
+    /// The average number of nodes on the path from the root to an element,
+    /// both included. This is the number of comparisons a successful search
+    /// makes on average.
+    pub average_depth: f64,
+    /// The estimated heap memory held by the nodes, including T.nil and the
+    /// spare nodes of `Tree::try_reserve`. Heap memory owned by the keys
+    /// themselves is not included.
+    pub heap_bytes: usize,
+}
+
+/// A broken invariant found by [`Tree::validate`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ValidationError {
+    /// The root is red.
+    RedRoot,
+    /// A red node has a red child.
+    RedChildOfRed,
+    /// Two paths from the root to a leaf have different numbers of black
+    /// nodes.
+    BlackHeightMismatch,
+    /// The keys are not sorted in strictly ascending order.
+    OutOfOrder,
+    /// A node does not point back to its parent.
+    BrokenParentLink,
+    /// The number of nodes differs from the length of the tree.
+    LengthMismatch { expected: usize, actual: usize },
+    /// The subtree size stored in a node is not the number of nodes below it.
+    SizeMismatch,
+    /// A shape passed to [`Tree::from_shape`] is higher than any red-black
+    /// tree can be.
+    TooDeep,
+}
+
+impl Display for ValidationError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            ValidationError::RedRoot => write!(f, "the root is red"),
+            ValidationError::RedChildOfRed => write!(f, "a red node has a red child"),
+            ValidationError::BlackHeightMismatch => {
+                write!(f, "paths to the leaves differ in black height")
+            }
+            ValidationError::OutOfOrder => write!(f, "keys are out of order"),
+            ValidationError::BrokenParentLink => {
+                write!(f, "a node does not point back to its parent")
+            }
+            ValidationError::LengthMismatch { expected, actual } => {
+                write!(f, "expected {expected} nodes, found {actual}")
+            }
+            ValidationError::SizeMismatch => {
+                write!(f, "a node stores the wrong subtree size")
+            }
+            ValidationError::TooDeep => write!(f, "the shape is too deep for a red-black tree"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ValidationError {}
+
+/// The result of one traversal of the tree, shared by [`Tree::stats`] and
+/// [`Tree::validate`].
+struct Survey {
+    stats: TreeStats,
+    /// The first broken invariant found, if any.
+    violation: Option<ValidationError>,
+}
+
+impl<T: PartialOrd + Clone + PartialEq + Debug + Default, O: Observer<T>> Tree<T, O> {
+    /// Measures the shape of the tree and the memory held by its nodes.
+    pub fn stats(&self) -> TreeStats {
+        self.survey(true).stats
+    }
+
+    /// Checks the red-black properties, the order of the keys, the parent
+    /// links, the subtree sizes and the length, returning the first violation
+    /// found.
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        match self.survey(true).violation {
+            Some(violation) => Err(violation),
+            None => Ok(()),
+        }
+    }
+
+    /// Like [`Tree::validate`], but for trees whose nodes are ordered by
+    /// position rather than by key, so that the keys are not compared.
+    pub(crate) fn validate_structure(&self) -> Result<(), ValidationError> {
+        match self.survey(false).violation {
+            Some(violation) => Err(violation),
+            None => Ok(()),
+        }
+    }
+
+    /// With the `paranoid` feature, validates the tree after the mutation
+    /// `operation`, called with `key` if it takes one, and panics with a
+    /// report if an invariant is broken. Does nothing otherwise.
+    pub(super) fn check_invariants(&self, operation: &str, key: Option<&T>) {
+        self.check(true, operation, key);
+    }
+
+    /// Like [`Tree::check_invariants`], but checks with
+    /// [`Tree::validate_structure`].
+    pub(super) fn check_structure(&self, operation: &str) {
+        self.check(false, operation, None);
+    }
+
+    fn check(&self, ordered: bool, operation: &str, key: Option<&T>) {
+        if !cfg!(feature = "paranoid") {
+            return;
+        }
+        let result = if ordered {
+            self.validate()
+        } else {
+            self.validate_structure()
+        };
+        if let Err(error) = result {
+            let call = match key {
+                Some(key) => format!("{}({:?})", operation, key),
+                None => format!("{}()", operation),
+            };
+            panic!("{} broke the red-black tree: {}\n{:?}", call, error, self);
+        }
+    }
+
+    /// Visits every node in order without recursion, so that even a
+    /// degenerate tree can be checked. The order of the keys is only checked
+    /// if `ordered` is set.
+    fn survey(&self, ordered: bool) -> Survey {
+        let mut stats = TreeStats {
+            len: 0,
+            height: 0,
+            black_height: 0,
+            red_nodes: 0,
+            black_nodes: 0,
+            average_depth: 0.0,
+            heap_bytes: 0,
+        };
+        let mut violation = None;
+        let mut report = |error: ValidationError| {
+            violation.get_or_insert(error);
+        };
+
+        if self.root.borrow().color == NodeColor::Red {
+            report(ValidationError::RedRoot);
+        }
+        if !self.root.borrow().is_nil() && !points_to(&self.root, &self.sentinel) {
+            report(ValidationError::BrokenParentLink);
+        }
+
+        // Nodes whose left subtree is being visited, with their depth and the
+        // number of black nodes from the root down to them.
+        let mut stack = vec![];
+        let mut previous: Option<Rc<RefCell<Node<T>>>> = None;
+        let mut leaf_black_height = None;
+        let mut total_depth = 0;
+        let (mut node, mut depth, mut blacks) = (self.root.clone(), 1, 0);
+        loop {
+            while !node.borrow().is_nil() {
+                if node.borrow().color == NodeColor::Black {
+                    blacks += 1;
+                }
+                let left = node.borrow().left().clone();
+                stack.push((node, depth, blacks));
+                node = left;
+                depth += 1;
+            }
+            match leaf_black_height {
+                None => leaf_black_height = Some(blacks),
+                Some(expected) if expected != blacks => {
+                    report(ValidationError::BlackHeightMismatch)
+                }
+                Some(_) => {}
+            }
+
+            let Some((current, current_depth, current_blacks)) = stack.pop() else {
+                break;
+            };
+            stats.len += 1;
+            stats.height = stats.height.max(current_depth);
+            total_depth += current_depth;
+
+            let node_ref = current.borrow();
+            if node_ref.color == NodeColor::Red {
+                stats.red_nodes += 1;
+            } else {
+                stats.black_nodes += 1;
+            }
+            for child in [node_ref.left(), node_ref.right()] {
+                if child.borrow().is_nil() {
+                    continue;
+                }
+                if node_ref.color == NodeColor::Red && child.borrow().color == NodeColor::Red {
+                    report(ValidationError::RedChildOfRed);
+                }
+                if !points_to(child, &current) {
+                    report(ValidationError::BrokenParentLink);
+                }
+            }
+            if node_ref.size != node_ref.left().borrow().size + node_ref.right().borrow().size + 1 {
+                report(ValidationError::SizeMismatch);
+            }
+            if let Some(previous) = previous.as_ref().filter(|_| ordered) {
+                if node_ref.key <= previous.borrow().key {
+                    report(ValidationError::OutOfOrder);
+                }
+            }
+
+            let right = node_ref.right().clone();
+            drop(node_ref);
+            previous = Some(current);
+            (node, depth, blacks) = (right, current_depth + 1, current_blacks);
+        }
+
+        if stats.len != self.length {
+            report(ValidationError::LengthMismatch {
+                expected: self.length,
+                actual: stats.len,
+            });
+        }
+        stats.black_height = leaf_black_height.unwrap_or(0);
+        if stats.len > 0 {
+            stats.average_depth = total_depth as f64 / stats.len as f64;
+        }
+        let nodes = stats.len + 1 + self.spare.len();
+        stats.heap_bytes = nodes * node_bytes::<T>();
+
+        Survey { stats, violation }
+    }
+}
+
+/// Whether the parent link of `node` points to `parent`.
+fn points_to<T>(node: &Rc<RefCell<Node<T>>>, parent: &Rc<RefCell<Node<T>>>) -> bool {
+    node.borrow()
+        .parent
+        .as_ref()
+        .is_some_and(|p| Rc::ptr_eq(p, parent))
+}
+
+/// Estimates the size of the allocation behind an `Rc<RefCell<Node<T>>>`:
+/// the node plus the strong and weak counts, ignoring padding.
+pub(super) fn node_bytes<T>() -> usize {
+    mem::size_of::<RefCell<Node<T>>>() + 2 * mem::size_of::<usize>()
+}
@@ -0,0 +1,161 @@
+use crate::{
+    node::NodeColor,
+    tree::{stats::node_bytes, Tree, TreeStats, ValidationError},
+};
+use proptest::prelude::*;
+use std::format;
+
+#[test]
+fn test_stats_of_empty_tree() {
+    let tree: Tree<i32> = Tree::new();
+
+    assert_eq!(
+        tree.stats(),
+        TreeStats {
+            len: 0,
+            height: 0,
+            black_height: 0,
+            red_nodes: 0,
+            black_nodes: 0,
+            average_depth: 0.0,
+            heap_bytes: node_bytes::<i32>(),
+        }
+    );
+    assert_eq!(tree.validate(), Ok(()));
+}
+
+#[test]
+fn test_stats_of_small_tree() {
+    let tree: Tree<i32> = [2, 1, 3].into_iter().collect();
+
+    let stats = tree.stats();
+
+    assert_eq!(stats.len, 3);
+    assert_eq!(stats.height, 2);
+    assert_eq!(stats.black_height, 1);
+    assert_eq!(stats.red_nodes, 2);
+    assert_eq!(stats.black_nodes, 1);
+    assert_eq!(stats.average_depth, 5.0 / 3.0);
+    assert_eq!(stats.heap_bytes, 4 * node_bytes::<i32>());
+}
+
+#[cfg(feature = "nightly")]
+#[test]
+fn test_heap_bytes_include_spare_nodes() {
+    let mut tree: Tree<i32> = [1].into_iter().collect();
+    let before = tree.stats().heap_bytes;
+
+    tree.try_reserve(2).unwrap();
+
+    let node = node_bytes::<i32>();
+    assert_eq!(tree.stats().heap_bytes, before + 2 * node);
+}
+
+#[test]
+fn test_validate_red_root() {
+    let tree: Tree<i32> = [1].into_iter().collect();
+    tree.root.borrow_mut().color = NodeColor::Red;
+
+    assert_eq!(tree.validate(), Err(ValidationError::RedRoot));
+}
+
+#[test]
+fn test_validate_red_child_of_red() {
+    let tree: Tree<i32> = [2, 1, 3, 4].into_iter().collect();
+    // 1 and 3 are black after the recoloring and 4 is a red child of 3.
+    // Turning both 1 and 3 red keeps the black heights equal.
+    for node in [tree.root.borrow().left(), tree.root.borrow().right()] {
+        node.borrow_mut().color = NodeColor::Red;
+    }
+
+    assert_eq!(tree.validate(), Err(ValidationError::RedChildOfRed));
+}
+
+#[test]
+fn test_validate_black_height_mismatch() {
+    let tree: Tree<i32> = [2, 1, 3].into_iter().collect();
+    let one = tree.root.borrow().left().clone();
+    one.borrow_mut().color = NodeColor::Black;
+
+    assert_eq!(tree.validate(), Err(ValidationError::BlackHeightMismatch));
+}
+
+#[test]
+fn test_validate_out_of_order() {
+    let tree: Tree<i32> = [2, 1, 3].into_iter().collect();
+    let one = tree.root.borrow().left().clone();
+    one.borrow_mut().key = 5;
+
+    assert_eq!(tree.validate(), Err(ValidationError::OutOfOrder));
+}
+
+#[test]
+fn test_validate_broken_parent_link() {
+    let tree: Tree<i32> = [2, 1, 3].into_iter().collect();
+    let one = tree.root.borrow().left().clone();
+    let three = tree.root.borrow().right().clone();
+    one.borrow_mut().set_parent(three);
+
+    assert_eq!(tree.validate(), Err(ValidationError::BrokenParentLink));
+}
+
+#[test]
+fn test_validate_size_mismatch() {
+    let tree: Tree<i32> = [2, 1, 3].into_iter().collect();
+    tree.root.borrow_mut().size = 2;
+
+    assert_eq!(tree.validate(), Err(ValidationError::SizeMismatch));
+}
+
+#[test]
+fn test_validate_length_mismatch() {
+    let mut tree: Tree<i32> = [2, 1, 3].into_iter().collect();
+    tree.length = 4;
+
+    assert_eq!(
+        tree.validate(),
+        Err(ValidationError::LengthMismatch {
+            expected: 4,
+            actual: 3
+        })
+    );
+}
+
+proptest! {
+    #[test]
+    fn test_stats_are_consistent(keys in prop::collection::vec(any::<i32>(), 1..300)) {
+        let tree: Tree<i32> = keys.iter().copied().collect();
+        let stats = tree.stats();
+
+        prop_assert_eq!(tree.validate(), Ok(()));
+        prop_assert_eq!(stats.len, keys.len());
+        prop_assert_eq!(stats.red_nodes + stats.black_nodes, keys.len());
+        // A red-black tree with n nodes is at most 2 log2(n + 1) high, and
+        // every path has at least as many black nodes as red ones.
+        let bound = 2.0 * ((keys.len() + 1) as f64).log2();
+        prop_assert!(stats.height as f64 <= bound);
+        prop_assert!(stats.height <= 2 * stats.black_height);
+        prop_assert!(stats.average_depth <= stats.height as f64);
+    }
+}
+
+#[cfg(feature = "paranoid")]
+#[test]
+#[should_panic(expected = "insert(0) broke the red-black tree: keys are out of order")]
+fn test_paranoid_insert_reports_operation_and_key() {
+    let mut tree: Tree<i32> = [2, 1, 3].into_iter().collect();
+    let one = tree.root.borrow().left().clone();
+    one.borrow_mut().key = 5;
+
+    tree.insert(0);
+}
+
+#[cfg(feature = "paranoid")]
+#[test]
+#[should_panic(expected = "delete(3) broke the red-black tree")]
+fn test_paranoid_delete_reports_operation_and_key() {
+    let mut tree: Tree<i32> = [2, 1, 3, 4].into_iter().collect();
+    tree.length += 1;
+
+    tree.delete(3);
+}
@@ -0,0 +1,220 @@
+use crate::{
+    node::NodeColor,
+    tree::{
+        shape::{color_name, write_json_string},
+        DotOptions, Observer, Shape,
+    },
+};
+use alloc::{format, string::String, vec::Vec};
+use core::fmt::{self, Debug, Display, Formatter, Write};
+
+#[cfg(test)]
+mod trace_tests;
+
+/// One step of an insertion or deletion, as reported to an [`Observer`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Step<T> {
+    /// The key was linked into the tree as a red leaf.
+    Insert(T),
+    /// The node holding the key was spliced out of the tree.
+    Delete(T),
+    /// A case of `insert_fix_up` fired.
+    InsertCase(u8),
+    /// A case of `delete_fix_up` fired.
+    DeleteCase(u8),
+    /// The tree was rotated left around the node holding the key.
+    LeftRotate(T),
+    /// The tree was rotated right around the node holding the key.
+    RightRotate(T),
+    /// The node holding the key was given a new color.
+    Recolor(T, NodeColor),
+}
+
+impl<T: Debug> Display for Step<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Step::Insert(key) => write!(f, "insert {:?}", key),
+            Step::Delete(key) => write!(f, "delete {:?}", key),
+            Step::InsertCase(case) => write!(f, "insert case {}", case),
+            Step::DeleteCase(case) => write!(f, "delete case {}", case),
+            Step::LeftRotate(key) => write!(f, "rotate left around {:?}", key),
+            Step::RightRotate(key) => write!(f, "rotate right around {:?}", key),
+            Step::Recolor(key, color) => write!(f, "color {:?} {}", key, color_name(color)),
+        }
+    }
+}
+
+/// A step together with the shape of the tree right after it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Frame<T> {
+    pub step: Step<T>,
+    pub shape: Shape<T>,
+}
+
+/// An [`Observer`] that records every step of the CLRS algorithms together
+/// with a snapshot of the tree, for teaching and animation.
+///
+/// ```
+/// use atlas_rb_tree::tree::{Step, Tracer, Tree};
+///
+/// let mut tree = Tree::with_observer(Tracer::new());
+/// tree.insert(1);
+/// tree.insert(2);
+/// tree.insert(3);
+///
+/// let steps: Vec<_> = tree.observer().frames().iter().map(|f| &f.step).collect();
+/// assert_eq!(steps[steps.len() - 1], &Step::LeftRotate(1));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Tracer<T> {
+    frames: Vec<Frame<T>>,
+    /// The step waiting for the snapshot that follows it.
+    pending: Option<Step<T>>,
+}
+
+impl<T> Tracer<T> {
+    pub fn new() -> Tracer<T> {
+        Self {
+            frames: Vec::new(),
+            pending: None,
+        }
+    }
+
+    /// Returns the recorded frames, oldest first.
+    pub fn frames(&self) -> &[Frame<T>] {
+        &self.frames
+    }
+
+    /// Forgets the recorded frames.
+    pub fn clear(&mut self) {
+        self.frames.clear();
+    }
+}
+
+impl<T: Debug> Tracer<T> {
+    /// Renders every frame as a Graphviz digraph labeled with its step, with
+    /// the default [`DotOptions`].
+    pub fn to_dot_frames(&self) -> Vec<String> {
+        self.to_dot_frames_with(&DotOptions::default())
+    }
+
+    /// Renders every frame as a Graphviz digraph labeled with its step.
+    pub fn to_dot_frames_with(&self, options: &DotOptions) -> Vec<String> {
+        self.frames
+            .iter()
+            .map(|frame| {
+                let mut out = String::new();
+                frame
+                    .shape
+                    .fmt_dot(&mut out, options, Some(&format!("{}", frame.step)), None)
+                    .expect("Writing to a String cannot fail");
+                out
+            })
+            .collect()
+    }
+
+    /// Renders the frames as a JSON array. Every frame is an object with the
+    /// step, the fields of the step and the shape, in which a node is an
+    /// object with `key`, `color`, `left` and `right`, and T.nil is `null`.
+    /// Keys are strings holding their `Debug` representation:
+    ///
+    /// ```json
+    /// [{"step": "insert", "key": "1", "shape": {"key": "1", "color": "red", "left": null, "right": null}}]
+    /// ```
+    pub fn to_json(&self) -> String {
+        let mut out = String::new();
+        self.fmt_json(&mut out)
+            .expect("Writing to a String cannot fail");
+        out
+    }
+
+    fn fmt_json<W: Write>(&self, out: &mut W) -> fmt::Result {
+        write!(out, "[")?;
+        for (index, frame) in self.frames.iter().enumerate() {
+            if index > 0 {
+                write!(out, ", ")?;
+            }
+            write!(out, "{{\"step\": ")?;
+            match &frame.step {
+                Step::Insert(key) => write_step(out, "insert", Some(key), None)?,
+                Step::Delete(key) => write_step(out, "delete", Some(key), None)?,
+                Step::InsertCase(case) => {
+                    write!(out, "\"insert_case\", \"case\": {}", case)?;
+                }
+                Step::DeleteCase(case) => {
+                    write!(out, "\"delete_case\", \"case\": {}", case)?;
+                }
+                Step::LeftRotate(key) => write_step(out, "left_rotate", Some(key), None)?,
+                Step::RightRotate(key) => write_step(out, "right_rotate", Some(key), None)?,
+                Step::Recolor(key, color) => write_step(out, "recolor", Some(key), Some(color))?,
+            }
+            write!(out, ", \"shape\": ")?;
+            frame.shape.fmt_json(out, |out, key| {
+                write_json_string(out, &format_args!("{:?}", key))
+            })?;
+            write!(out, "}}")?;
+        }
+        write!(out, "]")
+    }
+}
+
+impl<T> Default for Tracer<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Clone> Observer<T> for Tracer<T> {
+    const SNAPSHOTS: bool = true;
+
+    fn on_insert(&mut self, key: &T) {
+        self.pending = Some(Step::Insert(key.clone()));
+    }
+
+    fn on_delete(&mut self, key: &T) {
+        self.pending = Some(Step::Delete(key.clone()));
+    }
+
+    fn on_left_rotate(&mut self, pivot: &T) {
+        self.pending = Some(Step::LeftRotate(pivot.clone()));
+    }
+
+    fn on_right_rotate(&mut self, pivot: &T) {
+        self.pending = Some(Step::RightRotate(pivot.clone()));
+    }
+
+    fn on_insert_case(&mut self, case: u8) {
+        self.pending = Some(Step::InsertCase(case));
+    }
+
+    fn on_delete_case(&mut self, case: u8) {
+        self.pending = Some(Step::DeleteCase(case));
+    }
+
+    fn on_recolor(&mut self, key: &T, color: &NodeColor) {
+        self.pending = Some(Step::Recolor(key.clone(), color.clone()));
+    }
+
+    fn on_snapshot(&mut self, shape: Shape<T>) {
+        if let Some(step) = self.pending.take() {
+            self.frames.push(Frame { step, shape });
+        }
+    }
+}
+
+fn write_step<W: Write, T: Debug>(
+    out: &mut W,
+    name: &str,
+    key: Option<&T>,
+    color: Option<&NodeColor>,
+) -> fmt::Result {
+    write!(out, "\"{}\"", name)?;
+    if let Some(key) = key {
+        write!(out, ", \"key\": ")?;
+        write_json_string(out, &format_args!("{:?}", key))?;
+    }
+    if let Some(color) = color {
+        write!(out, ", \"color\": \"{}\"", color_name(color))?;
+    }
+    Ok(())
+}
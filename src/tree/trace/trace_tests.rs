@@ -0,0 +1,112 @@
+use crate::tree::{NodeColor, Shape, Step, Tracer, Tree};
+use std::{vec, vec::Vec};
+
+fn traced(keys: impl IntoIterator<Item = i32>) -> Tree<i32, Tracer<i32>> {
+    let mut tree = Tree::with_observer(Tracer::new());
+    for key in keys {
+        tree.insert(key);
+    }
+    tree
+}
+
+fn steps(tree: &Tree<i32, Tracer<i32>>) -> Vec<Step<i32>> {
+    tree.observer()
+        .frames()
+        .iter()
+        .map(|frame| frame.step.clone())
+        .collect()
+}
+
+#[test]
+fn test_trace_insertions() {
+    let tree = traced([1, 2, 3]);
+
+    assert_eq!(
+        steps(&tree),
+        vec![
+            Step::Insert(1),
+            Step::Recolor(1, NodeColor::Black),
+            Step::Insert(2),
+            Step::Insert(3),
+            Step::InsertCase(6),
+            Step::Recolor(2, NodeColor::Black),
+            Step::Recolor(1, NodeColor::Red),
+            Step::LeftRotate(1),
+        ]
+    );
+}
+
+#[test]
+fn test_trace_deletion() {
+    let mut tree = traced(1..=10);
+    tree.observer_mut().clear();
+
+    tree.delete(1);
+
+    assert_eq!(
+        steps(&tree),
+        vec![
+            Step::Delete(1),
+            Step::DeleteCase(2),
+            Step::Recolor(3, NodeColor::Red),
+            Step::DeleteCase(4),
+            Step::Recolor(8, NodeColor::Black),
+            Step::LeftRotate(4),
+        ]
+    );
+}
+
+#[test]
+fn test_frames_hold_shape_after_step() {
+    let tree = traced([1, 2, 3]);
+    let frames = tree.observer().frames();
+
+    // The new leaf is red until the root is recolored.
+    let Shape::Node { color, .. } = &frames[0].shape else {
+        panic!("Expected a node");
+    };
+    assert_eq!(*color, NodeColor::Red);
+    assert_eq!(frames.last().unwrap().shape, tree.to_shape());
+}
+
+#[test]
+fn test_dot_frames_are_labeled() {
+    let tree = traced([1, 2, 3]);
+
+    let frames = tree.observer().to_dot_frames();
+
+    assert_eq!(frames.len(), 8);
+    assert!(frames[0].contains("    label=\"insert 1\";\n"));
+    assert!(frames[7].contains("    label=\"rotate left around 1\";\n"));
+    assert!(frames[7].contains("    n0 [label=\"2\", fillcolor=black];\n"));
+}
+
+#[test]
+fn test_to_json() {
+    let tree = traced([1, 2]);
+
+    assert_eq!(
+        tree.observer().to_json(),
+        [
+            "[{\"step\": \"insert\", \"key\": \"1\", \"shape\": ",
+            "{\"key\": \"1\", \"color\": \"red\", \"left\": null, \"right\": null}}, ",
+            "{\"step\": \"recolor\", \"key\": \"1\", \"color\": \"black\", \"shape\": ",
+            "{\"key\": \"1\", \"color\": \"black\", \"left\": null, \"right\": null}}, ",
+            "{\"step\": \"insert\", \"key\": \"2\", \"shape\": ",
+            "{\"key\": \"1\", \"color\": \"black\", \"left\": null, \"right\": ",
+            "{\"key\": \"2\", \"color\": \"red\", \"left\": null, \"right\": null}}}]",
+        ]
+        .concat()
+    );
+}
+
+#[test]
+fn test_json_escapes_keys() {
+    let mut tree = Tree::with_observer(Tracer::new());
+    tree.insert("a\"b");
+
+    assert!(tree
+        .observer()
+        .to_json()
+        .contains("\"key\": \"\\\"a\\\\\\\"b\\\"\""));
+}
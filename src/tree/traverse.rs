@@ -0,0 +1,215 @@
+use crate::{
+    node::{Node, NodeColor},
+    tree::{NoopObserver, Observer, Tree},
+};
+use alloc::{collections::VecDeque, rc::Rc, vec, vec::Vec};
+use core::{cell::RefCell, fmt::Debug};
+
+#[cfg(test)]
+mod traverse_tests;
+
+/// A read-only view of a node visited by a structural traversal.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NodeView<'a, T> {
+    pub key: &'a T,
+    pub color: NodeColor,
+    /// The number of edges between the node and the root, 0 for the root.
+    pub depth: usize,
+    /// Whether the node is the left child of its parent. False for the root.
+    pub is_left_child: bool,
+}
+
+/// A node waiting to be visited, with the position it was reached at.
+struct Pending<T> {
+    node: Rc<RefCell<Node<T>>>,
+    depth: usize,
+    is_left_child: bool,
+}
+
+impl<T> Pending<T> {
+    fn root(node: &Rc<RefCell<Node<T>>>) -> Option<Pending<T>> {
+        if node.borrow().is_nil() {
+            return None;
+        }
+        Some(Pending {
+            node: node.clone(),
+            depth: 0,
+            is_left_child: false,
+        })
+    }
+
+    /// Returns the children that are not T.nil, left first.
+    fn children(&self) -> impl Iterator<Item = Pending<T>> {
+        let node = self.node.borrow();
+        let depth = self.depth + 1;
+        [(node.left().clone(), true), (node.right().clone(), false)]
+            .into_iter()
+            .filter(|(child, _)| !child.borrow().is_nil())
+            .map(move |(node, is_left_child)| Pending {
+                node,
+                depth,
+                is_left_child,
+            })
+    }
+}
+
+/// An iterator over the keys of a [`Tree`] in ascending order. Created by
+/// [`Tree::iter`].
+pub struct Iter<'a, T, O = NoopObserver> {
+    tree: &'a Tree<T, O>,
+    /// The nodes whose key and right subtree have not been visited yet, the
+    /// next one on top.
+    stack: Vec<Rc<RefCell<Node<T>>>>,
+}
+
+/// An iterator over the nodes of a [`Tree`] level by level, each level from
+/// left to right. Created by [`Tree::iter_level_order`].
+pub struct LevelOrder<'a, T, O = NoopObserver> {
+    tree: &'a Tree<T, O>,
+    queue: VecDeque<Pending<T>>,
+}
+
+/// An iterator over the nodes of a [`Tree`] with every node before its left
+/// and then its right subtree. Created by [`Tree::iter_preorder`].
+pub struct PreOrder<'a, T, O = NoopObserver> {
+    tree: &'a Tree<T, O>,
+    stack: Vec<Pending<T>>,
+}
+
+/// An iterator over the nodes of a [`Tree`] with every node after its left
+/// and then its right subtree. Created by [`Tree::iter_postorder`].
+pub struct PostOrder<'a, T, O = NoopObserver> {
+    tree: &'a Tree<T, O>,
+    /// Nodes together with whether their children have been pushed already.
+    stack: Vec<(Pending<T>, bool)>,
+}
+
+impl<T: PartialOrd + Clone + PartialEq + Debug + Default, O: Observer<T>> Tree<T, O> {
+    /// Returns an iterator over the keys in ascending order.
+    pub fn iter(&self) -> Iter<'_, T, O> {
+        let mut iter = Iter {
+            tree: self,
+            stack: vec![],
+        };
+        iter.push_left_spine(self.root.clone());
+        iter
+    }
+
+    /// Returns an iterator over the nodes level by level, starting at the
+    /// root, each level from left to right.
+    pub fn iter_level_order(&self) -> LevelOrder<'_, T, O> {
+        LevelOrder {
+            tree: self,
+            queue: Pending::root(&self.root).into_iter().collect(),
+        }
+    }
+
+    /// Returns an iterator over the nodes in pre-order: every node before its
+    /// left and then its right subtree.
+    pub fn iter_preorder(&self) -> PreOrder<'_, T, O> {
+        PreOrder {
+            tree: self,
+            stack: Pending::root(&self.root).into_iter().collect(),
+        }
+    }
+
+    /// Returns an iterator over the nodes in post-order: every node after its
+    /// left and then its right subtree.
+    pub fn iter_postorder(&self) -> PostOrder<'_, T, O> {
+        PostOrder {
+            tree: self,
+            stack: Pending::root(&self.root)
+                .map(|pending| (pending, false))
+                .into_iter()
+                .collect(),
+        }
+    }
+
+    fn view(&self, pending: &Pending<T>) -> NodeView<'_, T> {
+        NodeView {
+            key: self.key_ref(&pending.node),
+            color: pending.node.borrow().color.clone(),
+            depth: pending.depth,
+            is_left_child: pending.is_left_child,
+        }
+    }
+}
+
+impl<T, O> Iter<'_, T, O> {
+    fn push_left_spine(&mut self, mut node: Rc<RefCell<Node<T>>>) {
+        while !node.borrow().is_nil() {
+            let left = node.borrow().left().clone();
+            self.stack.push(node);
+            node = left;
+        }
+    }
+}
+
+impl<'a, T: PartialOrd + Clone + PartialEq + Debug + Default, O: Observer<T>> Iterator
+    for Iter<'a, T, O>
+{
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        let node = self.stack.pop()?;
+        let right = node.borrow().right().clone();
+        self.push_left_spine(right);
+        Some(self.tree.key_ref(&node))
+    }
+}
+
+impl<'a, T: PartialOrd + Clone + PartialEq + Debug + Default, O: Observer<T>> IntoIterator
+    for &'a Tree<T, O>
+{
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T, O>;
+
+    fn into_iter(self) -> Iter<'a, T, O> {
+        self.iter()
+    }
+}
+
+impl<'a, T: PartialOrd + Clone + PartialEq + Debug + Default, O: Observer<T>> Iterator
+    for LevelOrder<'a, T, O>
+{
+    type Item = NodeView<'a, T>;
+
+    fn next(&mut self) -> Option<NodeView<'a, T>> {
+        let pending = self.queue.pop_front()?;
+        self.queue.extend(pending.children());
+        Some(self.tree.view(&pending))
+    }
+}
+
+impl<'a, T: PartialOrd + Clone + PartialEq + Debug + Default, O: Observer<T>> Iterator
+    for PreOrder<'a, T, O>
+{
+    type Item = NodeView<'a, T>;
+
+    fn next(&mut self) -> Option<NodeView<'a, T>> {
+        let pending = self.stack.pop()?;
+        // Push the right child first so that the left one is visited first.
+        let children: Vec<_> = pending.children().collect();
+        self.stack.extend(children.into_iter().rev());
+        Some(self.tree.view(&pending))
+    }
+}
+
+impl<'a, T: PartialOrd + Clone + PartialEq + Debug + Default, O: Observer<T>> Iterator
+    for PostOrder<'a, T, O>
+{
+    type Item = NodeView<'a, T>;
+
+    fn next(&mut self) -> Option<NodeView<'a, T>> {
+        loop {
+            let (pending, expanded) = self.stack.pop()?;
+            if expanded {
+                return Some(self.tree.view(&pending));
+            }
+            let children: Vec<_> = pending.children().collect();
+            self.stack.push((pending, true));
+            self.stack
+                .extend(children.into_iter().rev().map(|child| (child, false)));
+        }
+    }
+}
@@ -0,0 +1,124 @@
+use crate::tree::{NodeColor, NodeView, Tree};
+use proptest::prelude::*;
+use std::{format, vec, vec::Vec};
+
+/// Builds the perfect tree
+///
+/// ```text
+///         4
+///       /   \
+///      2     6
+///     / \   / \
+///    1   3 5   7
+/// ```
+///
+/// with 4, 2 and 6 black and the leaves red.
+fn perfect_tree() -> Tree<i32> {
+    let mut tree = Tree::new();
+    for key in [4, 2, 6, 1, 3, 5, 7] {
+        tree.insert(key);
+    }
+    tree
+}
+
+fn keys<'a>(views: impl Iterator<Item = NodeView<'a, i32>>) -> Vec<i32> {
+    views.map(|view| *view.key).collect()
+}
+
+#[test]
+fn test_iter_is_sorted() {
+    let tree = perfect_tree();
+
+    assert_eq!(
+        tree.iter().copied().collect::<Vec<_>>(),
+        (1..=7).collect::<Vec<_>>()
+    );
+    assert_eq!((&tree).into_iter().count(), 7);
+}
+
+#[test]
+fn test_iter_level_order() {
+    let tree = perfect_tree();
+
+    assert_eq!(keys(tree.iter_level_order()), vec![4, 2, 6, 1, 3, 5, 7]);
+}
+
+#[test]
+fn test_iter_preorder() {
+    let tree = perfect_tree();
+
+    assert_eq!(keys(tree.iter_preorder()), vec![4, 2, 1, 3, 6, 5, 7]);
+}
+
+#[test]
+fn test_iter_postorder() {
+    let tree = perfect_tree();
+
+    assert_eq!(keys(tree.iter_postorder()), vec![1, 3, 2, 5, 7, 6, 4]);
+}
+
+#[test]
+fn test_node_views() {
+    let tree = perfect_tree();
+    let views: Vec<_> = tree.iter_level_order().collect();
+
+    assert_eq!(
+        views[0],
+        NodeView {
+            key: &4,
+            color: NodeColor::Black,
+            depth: 0,
+            is_left_child: false,
+        }
+    );
+    assert_eq!(
+        views[4],
+        NodeView {
+            key: &3,
+            color: NodeColor::Red,
+            depth: 2,
+            is_left_child: false,
+        }
+    );
+    assert!(views[5].is_left_child);
+}
+
+#[test]
+fn test_traversals_of_empty_tree() {
+    let tree: Tree<i32> = Tree::new();
+
+    assert_eq!(tree.iter().next(), None);
+    assert_eq!(tree.iter_level_order().next(), None);
+    assert_eq!(tree.iter_preorder().next(), None);
+    assert_eq!(tree.iter_postorder().next(), None);
+}
+
+proptest! {
+    #[test]
+    fn test_traversals_visit_every_node(keys in prop::collection::vec(any::<i16>(), 0..200)) {
+        let mut tree = Tree::new();
+        for key in &keys {
+            tree.insert(*key);
+        }
+        let mut sorted = keys.clone();
+        sorted.sort();
+        sorted.dedup();
+
+        prop_assert_eq!(tree.iter().copied().collect::<Vec<_>>(), sorted.clone());
+        for mut visited in [
+            tree.iter_level_order().map(|view| *view.key).collect::<Vec<_>>(),
+            tree.iter_preorder().map(|view| *view.key).collect(),
+            tree.iter_postorder().map(|view| *view.key).collect(),
+        ] {
+            visited.sort();
+            prop_assert_eq!(&visited, &sorted);
+        }
+
+        // Levels never go back up, and the root comes last in post-order.
+        let depths: Vec<_> = tree.iter_level_order().map(|view| view.depth).collect();
+        prop_assert!(depths.windows(2).all(|pair| pair[0] <= pair[1]));
+        if let Some(last) = tree.iter_postorder().last() {
+            prop_assert_eq!(last.depth, 0);
+        }
+    }
+}
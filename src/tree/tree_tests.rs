@@ -1,12 +1,16 @@
 use crate::{
-    node::{Node, NodeColor},
-    tree::Tree,
+    node::Node,
+    tree::{NoopObserver, Tree},
 };
 use proptest::prelude::*;
 use std::{
     cell::RefCell,
     cmp::{max, min},
+    fmt::Debug,
+    format,
     rc::Rc,
+    string::ToString,
+    vec::Vec,
 };
 
 impl<T> Tree<T> {
@@ -16,87 +20,22 @@ impl<T> Tree<T> {
             root,
             sentinel,
             length: 0,
+            spare: Vec::new(),
+            generation: 0,
+            augmentation: None,
+            observer: NoopObserver,
         }
     }
 }
 
-fn is_red<T>(node: Rc<RefCell<Node<T>>>) -> bool {
-    node.borrow().color == NodeColor::Red
-}
-
-fn is_black<T>(node: Rc<RefCell<Node<T>>>) -> bool
-where
-    T: Default,
-{
-    !is_red(node)
-}
-
-fn check_red_node_property<T>(node: Rc<RefCell<Node<T>>>) -> bool
-where
-    T: Default,
-{
-    match node.borrow().is_nil() {
-        false => {
-            if is_red(node.clone()) {
-                // Ensure children are black
-                is_black(node.borrow().left().clone()) && is_black(node.borrow().right().clone())
-            } else {
-                // Recursively check children
-                check_red_node_property(node.borrow().left().clone())
-                    && check_red_node_property(node.borrow().right().clone())
-            }
-        }
-        true => true, // Nil nodes are black
-    }
-}
-
-fn count_black_nodes<T>(node: Rc<RefCell<Node<T>>>) -> Vec<i32>
+pub(crate) fn assert_red_black_tree_properties<T>(tree: &Tree<T>)
 where
-    T: Default,
-{
-    match node.borrow().is_nil() {
-        false => {
-            let left_counts = count_black_nodes(node.borrow().left().clone());
-            let right_counts = count_black_nodes(node.borrow().right().clone());
-
-            // Combine and adjust for current node
-            let mut counts = vec![];
-            for count in left_counts.into_iter().chain(right_counts.into_iter()) {
-                counts.push(
-                    count
-                        + if node.borrow().color == NodeColor::Black {
-                            1
-                        } else {
-                            0
-                        },
-                );
-            }
-            counts
-        }
-        true => vec![1], // Nil nodes are black, count as one black node
-    }
-}
-
-fn assert_red_black_tree_properties<T>(tree: &Tree<T>)
-where
-    T: Default,
+    T: PartialOrd + Clone + PartialEq + Debug + Default,
 {
     if tree.root.borrow().is_nil() {
         panic!("Assertions on empty red-black trees cause a panic for your own sake")
     }
-    // Root property
-    assert_eq!(tree.root.borrow().color, NodeColor::Black);
-
-    // Red node property
-    assert!(check_red_node_property(tree.root.clone()));
-
-    // Black height property
-    let black_node_counts = count_black_nodes(tree.root.clone());
-    // Ensure all paths have the same number of black nodes
-    assert_eq!(
-        black_node_counts.iter().min(),
-        black_node_counts.iter().max()
-    );
+    assert_eq!(tree.validate(), Ok(()));
 }
 
 #[test]
@@ -311,6 +250,89 @@ fn test_delete_maintains_properties() {
     assert_eq!(tree.length, 3);
 }
 
+#[test]
+fn test_insert_rejects_duplicates() {
+    let mut tree = Tree::new();
+
+    assert!(tree.insert(1));
+    assert!(tree.insert(2));
+    assert!(!tree.insert(1));
+
+    assert_eq!(tree.len(), 2);
+    tree.delete(1);
+    assert!(!tree.contains_key(1));
+    assert_red_black_tree_properties(&tree);
+}
+
+/// Ordered by `id` alone, so that equal elements can differ in `payload`.
+#[derive(Debug, Clone, Default)]
+struct Record {
+    id: u32,
+    payload: &'static str,
+}
+
+impl PartialEq for Record {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl PartialOrd for Record {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        self.id.partial_cmp(&other.id)
+    }
+}
+
+#[test]
+fn test_replace_swaps_equal_element() {
+    let mut tree = Tree::new();
+    for id in 0..10 {
+        tree.insert(Record { id, payload: "old" });
+    }
+
+    let replaced = tree.replace(Record {
+        id: 4,
+        payload: "new",
+    });
+
+    assert_eq!(replaced.map(|record| record.payload), Some("old"));
+    assert_eq!(tree.len(), 10);
+    let payloads: Vec<_> = tree.iter().map(|record| record.payload).collect();
+    assert_eq!(
+        payloads.iter().filter(|payload| **payload == "new").count(),
+        1
+    );
+    assert_eq!(payloads[4], "new");
+    assert_red_black_tree_properties(&tree);
+}
+
+#[test]
+fn test_replace_inserts_missing_element() {
+    let mut tree = Tree::new();
+    tree.insert(1);
+
+    assert_eq!(tree.replace(2), None);
+
+    assert_eq!(tree.len(), 2);
+    assert!(tree.contains_key(2));
+}
+
+#[test]
+fn test_insert_keeps_existing_equal_element() {
+    let mut tree = Tree::new();
+    tree.insert(Record {
+        id: 1,
+        payload: "first",
+    });
+
+    assert!(!tree.insert(Record {
+        id: 1,
+        payload: "second"
+    }));
+
+    assert_eq!(tree.first().map(|record| record.payload), Some("first"));
+}
+
 #[test]
 fn test_contains_key() {
     let mut tree = Tree::new();
@@ -417,3 +439,45 @@ proptest! {
         prop_assert_eq!(actual_max, Some(expected_max));
     }
 }
+
+#[test]
+fn test_first_and_last() {
+    let mut tree = Tree::new();
+    assert_eq!(tree.first(), None);
+    assert_eq!(tree.last(), None);
+
+    tree.insert(3);
+    tree.insert(1);
+    tree.insert(2);
+
+    assert_eq!(tree.first(), Some(&1));
+    assert_eq!(tree.last(), Some(&3));
+}
+
+#[test]
+fn test_pop_first_and_pop_last() {
+    let mut tree = Tree::new();
+    assert_eq!(tree.pop_first(), None);
+    assert_eq!(tree.pop_last(), None);
+
+    for key in [5, 15, 2, 7, 12, 17] {
+        tree.insert(key);
+    }
+
+    assert_eq!(tree.pop_first(), Some(2));
+    assert_red_black_tree_properties(&tree);
+    assert_eq!(tree.pop_last(), Some(17));
+    assert_red_black_tree_properties(&tree);
+    assert_eq!(tree.len(), 4);
+    assert_eq!(tree.first(), Some(&5));
+    assert_eq!(tree.last(), Some(&15));
+}
+
+#[test]
+fn test_collect() {
+    let tree: Tree<i32> = [3, 1, 2, 1].into_iter().collect();
+
+    assert_red_black_tree_properties(&tree);
+    assert_eq!(tree.len(), 3);
+    assert_eq!(tree.iter().copied().collect::<Vec<_>>(), [1, 2, 3]);
+}